@@ -1,8 +1,157 @@
-use crate::prelude::*;
+use crate::{prelude::*, spawn};
 
+pub mod collection;
+pub mod config;
 pub mod data;
+pub mod dex;
+pub mod export;
 pub mod help;
 pub mod ping;
+pub mod progress;
+pub mod purge;
+pub mod roll;
+
+pub const COMMANDS: &[&dyn SlashCommand] = &[
+    &collection::Handler,
+    &config::Handler,
+    &data::Handler,
+    &dex::Handler,
+    &export::Handler,
+    &help::Handler,
+    &ping::Handler,
+    &progress::Handler,
+    &purge::Handler,
+    &roll::Handler,
+    &spawn::Handler,
+];
+
+pub fn find_command(name: &str) -> Option<&'static dyn SlashCommand> {
+    COMMANDS.iter().copied().find(|c| c.name() == name)
+}
+
+#[async_trait]
+pub trait SlashCommand: Sync {
+    fn name(&self) -> &'static str;
+    fn create(&self) -> Option<CreateCommand>;
+
+    async fn command(&self, _: &Events, _: &Context, _: &CommandInteraction) -> Result<()> {
+        Err(anyhow!("{} does not handle commands", self.name()))
+    }
+    async fn component(
+        &self,
+        _: &Events,
+        _: &Context,
+        _: &ComponentInteraction,
+        _: &CustomId<'_>,
+    ) -> Result<()> {
+        Err(anyhow!("{} does not handle components", self.name()))
+    }
+    async fn modal(
+        &self,
+        _: &Events,
+        _: &Context,
+        _: &ModalInteraction,
+        _: &CustomId<'_>,
+    ) -> Result<()> {
+        Err(anyhow!("{} does not handle modals", self.name()))
+    }
+    async fn autocomplete(
+        &self,
+        events: &Events,
+        context: &Context,
+        autocomplete: &CommandInteraction,
+    ) -> Result<()> {
+        autocomplete_names(events, context, autocomplete).await
+    }
+}
+
+#[macro_export]
+macro_rules! handler {
+    (@create) => {
+        None
+    };
+    (@create $create:path) => {
+        Some($create())
+    };
+    (
+        $(create: $create:path,)?
+        $(command: $command:path,)?
+        $(component: $component:path,)?
+        $(modal: $modal:path,)?
+    ) => {
+        pub struct Handler;
+
+        #[async_trait]
+        impl SlashCommand for Handler {
+            fn name(&self) -> &'static str {
+                NAME
+            }
+            fn create(&self) -> Option<CreateCommand> {
+                $crate::handler!(@create $($create)?)
+            }
+
+            $(
+                async fn command(
+                    &self,
+                    events: &Events,
+                    context: &Context,
+                    interaction: &CommandInteraction,
+                ) -> Result<()> {
+                    $command(events, context, interaction).await
+                }
+            )?
+            $(
+                async fn component(
+                    &self,
+                    events: &Events,
+                    context: &Context,
+                    interaction: &ComponentInteraction,
+                    custom_id: &CustomId<'_>,
+                ) -> Result<()> {
+                    $component(events, context, interaction, custom_id).await
+                }
+            )?
+            $(
+                async fn modal(
+                    &self,
+                    events: &Events,
+                    context: &Context,
+                    interaction: &ModalInteraction,
+                    custom_id: &CustomId<'_>,
+                ) -> Result<()> {
+                    $modal(events, context, interaction, custom_id).await
+                }
+            )?
+        }
+    };
+}
+
+pub async fn autocomplete_names(
+    events: &Events,
+    context: &Context,
+    autocomplete: &CommandInteraction,
+) -> Result<()> {
+    let Some(option) = autocomplete.data.autocomplete() else {
+        return Err(anyhow!("missing focused option"));
+    };
+    let Some(kind) = NameKind::from_option(option.name) else {
+        return Err(anyhow!(
+            "unsupported autocomplete option \"{}\"",
+            option.name
+        ));
+    };
+
+    let mut builder = CreateAutocompleteResponse::new();
+
+    for entry in events.names.search(kind, option.value) {
+        builder = builder.add_string_choice(&entry.label, entry.id.to_string());
+    }
+
+    let builder = CreateInteractionResponse::Autocomplete(builder);
+    autocomplete.create_response(context, builder).await?;
+
+    Ok(())
+}
 
 macro_rules! getter {
     ($id:ident($inner:path) -> $output:ty) => {
@@ -0,0 +1,326 @@
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "collection";
+pub const PAGE_SIZE: usize = 10;
+pub const TYPES: [&str; 18] = [
+    "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
+    "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
+];
+
+pub fn new() -> CreateCommand {
+    let mut sort = CreateCommandOption::new(
+        CommandOptionType::String,
+        "sort",
+        "The order to display the collection in",
+    );
+    let mut kind = CreateCommandOption::new(
+        CommandOptionType::String,
+        "type",
+        "Only display Pokémon of the given type",
+    );
+
+    for value in Sort::ALL {
+        sort = sort.add_string_choice(value.label(), value.key());
+    }
+    for value in TYPES {
+        kind = kind.add_string_choice(value, value);
+    }
+
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Displays a user's Pokémon collection")
+        .dm_permission(true)
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::User,
+            "user",
+            "The user whose collection to display",
+        ))
+        .add_option(sort)
+        .add_option(kind)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "species",
+                "Only display Pokémon of the given species",
+            )
+            .set_autocomplete(true),
+        )
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    command.defer_ephemeral(context).await?;
+
+    let options = command.data.options();
+    let owner = get_user(&options, "user").map_or_else(|_| command.user.clone(), |u| u.0.clone());
+    let sort = get_str(&options, "sort").map_or(Ok(Sort::default()), Sort::from_key)?;
+    let filter = get_str(&options, "type").ok().map(ToString::to_string);
+    let species = match get_str(&options, "species") {
+        Ok(value) => Some(events.names.resolve(NameKind::Species, value)?.id),
+        Err(_) => None,
+    };
+    let view = CollectionView::new(&events.collections, owner, 0, sort, filter, species).await?;
+
+    let buttons = view.try_to_buttons(false, ())?;
+    let builder = CreateInteractionResponseFollowup::new()
+        .embed(view.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+    command.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+pub async fn component(
+    events: &Events,
+    context: &Context,
+    component: &ComponentInteraction,
+    custom_id: &CustomId<'_>,
+) -> Result<()> {
+    let owner = UserId::new(custom_id.data_as(0)?).to_user(context).await?;
+    let page = custom_id.data_as(1)?;
+    let sort = Sort::from_key(custom_id.data(2)?)?;
+    let filter = Some(custom_id.data(3)?).filter(|f| !f.is_empty());
+    let filter = filter.map(ToString::to_string);
+    let species = Some(custom_id.data(4)?).filter(|s| !s.is_empty());
+    let species = species.map(str::parse).transpose()?;
+    let mut view =
+        CollectionView::new(&events.collections, owner, page, sort, filter, species).await?;
+
+    match custom_id.name {
+        "first" => view.page = 0,
+        "prev" => view.page = view.page.saturating_sub(1),
+        "next" => view.page += 1,
+        "last" => view.page = view.pages() - 1,
+        "sort" => {
+            view.sort = view.sort.next();
+            view.page = 0;
+        }
+        _ => return Err(anyhow!("invalid custom identifier name")),
+    }
+
+    view.page = view.page.min(view.pages() - 1);
+
+    let buttons = view.try_to_buttons(false, ())?;
+    let message = CreateInteractionResponseMessage::new()
+        .embed(view.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+
+    let builder = CreateInteractionResponse::UpdateMessage(message);
+    component.create_response(context, builder).await?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Dex,
+    Date,
+    Level,
+    Name,
+}
+
+impl Sort {
+    pub const ALL: [Self; 4] = [Self::Dex, Self::Date, Self::Level, Self::Name];
+
+    pub fn from_key(key: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.key() == key)
+            .ok_or_else(|| anyhow!("invalid sort key \"{key}\""))
+    }
+
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Dex => "dex",
+            Self::Date => "date",
+            Self::Level => "level",
+            Self::Name => "name",
+        }
+    }
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Dex => "Dex Number",
+            Self::Date => "Catch Date",
+            Self::Level => "Level",
+            Self::Name => "Name",
+        }
+    }
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Dex => Self::Date,
+            Self::Date => Self::Level,
+            Self::Level => Self::Name,
+            Self::Name => Self::Dex,
+        }
+    }
+
+    pub fn sort(self, entries: &mut [&Caught]) {
+        match self {
+            Self::Dex => entries.sort_by_key(|c| c.species_id),
+            Self::Date => entries.sort_by_key(|c| c.caught_at),
+            Self::Level => entries.sort_by_key(|c| std::cmp::Reverse(c.level)),
+            Self::Name => entries.sort_by_key(|c| c.display_name().to_lowercase()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CollectionView {
+    pub owner: User,
+    pub collection: Collection,
+    pub page: usize,
+    pub sort: Sort,
+    pub filter: Option<String>,
+    pub species: Option<i64>,
+}
+
+impl CollectionView {
+    pub async fn new(
+        store: &Store<Collection>,
+        owner: User,
+        page: usize,
+        sort: Sort,
+        filter: Option<String>,
+        species: Option<i64>,
+    ) -> Result<Self> {
+        let key = owner.id.to_string();
+        let collection = store.lock(&key).await?.clone();
+
+        Ok(Self {
+            owner,
+            collection,
+            page,
+            sort,
+            filter,
+            species,
+        })
+    }
+
+    pub fn entries(&self) -> Vec<&Caught> {
+        let mut entries: Vec<_> = self
+            .collection
+            .pokemon
+            .iter()
+            .filter(|c| self.filter.as_ref().is_none_or(|f| c.types.contains(f)))
+            .filter(|c| self.species.is_none_or(|s| c.species_id == s))
+            .collect();
+
+        self.sort.sort(&mut entries);
+        entries
+    }
+    pub fn pages(&self) -> usize {
+        self.entries().len().div_ceil(PAGE_SIZE).max(1)
+    }
+}
+
+impl ToEmbed for CollectionView {
+    type Args = ();
+
+    fn to_embed(&self, _: Self::Args) -> CreateEmbed {
+        let entries = self.entries();
+        let count = entries.len();
+        let lines: Vec<_> = entries
+            .into_iter()
+            .skip(self.page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|c| {
+                let star = if c.shiny { " ✨" } else { "" };
+
+                format!(
+                    "**{}**{star} - #{} (Lv. {})",
+                    c.display_name(),
+                    c.species_id,
+                    c.level
+                )
+            })
+            .collect();
+
+        let description = if lines.is_empty() {
+            "> Looks like there aren't any Pokémon here!".to_string()
+        } else {
+            lines.join("\n")
+        };
+
+        let mut footer = format!(
+            "Page {} of {} • {count} Pokémon • Sorted by {}",
+            self.page + 1,
+            self.pages(),
+            self.sort.label()
+        );
+
+        if let Some(filter) = &self.filter {
+            footer.push_str(&format!(" • Type: {filter}"));
+        }
+        if let Some(species) = self.species {
+            footer.push_str(&format!(" • Species: #{species}"));
+        }
+
+        let author = CreateEmbedAuthor::new(self.owner.tag()).icon_url(self.owner.face());
+
+        CreateEmbed::new()
+            .author(author)
+            .color(Color::ROSEWATER)
+            .description(description)
+            .footer(CreateEmbedFooter::new(footer))
+            .title(format!("{}'s Collection", self.owner.name))
+    }
+}
+
+impl TryToButtons for CollectionView {
+    type Args = ();
+
+    fn try_to_buttons(&self, disabled: bool, _: Self::Args) -> Result<Vec<CreateButton>> {
+        let filter = self.filter.as_deref().unwrap_or_default();
+        let species = self
+            .species
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let last = self.page + 1 >= self.pages();
+
+        let custom_id = |name: &'static str| -> Result<String> {
+            let mut custom_id = CustomId::new(NAME, name);
+
+            custom_id.push_data(self.owner.id.to_string())?;
+            custom_id.push_data(self.page.to_string())?;
+            custom_id.push_data(self.sort.key())?;
+            custom_id.push_data(filter)?;
+            custom_id.push_data(species.as_str())?;
+
+            Ok(custom_id.to_string())
+        };
+
+        Ok(vec![
+            CreateButton::new(custom_id("first")?)
+                .disabled(disabled || self.page == 0)
+                .emoji('⏮')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("prev")?)
+                .disabled(disabled || self.page == 0)
+                .emoji('◀')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("next")?)
+                .disabled(disabled || last)
+                .emoji('▶')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("last")?)
+                .disabled(disabled || last)
+                .emoji('⏭')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("sort")?)
+                .disabled(disabled)
+                .label(format!("Sort: {}", self.sort.label()))
+                .style(ButtonStyle::Primary),
+        ])
+    }
+}
+
+handler! {
+    create: new,
+    command: command,
+    component: component,
+}
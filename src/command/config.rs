@@ -0,0 +1,124 @@
+use serenity::all::ChannelType;
+
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "config";
+
+pub fn new() -> CreateCommand {
+    let channel = || {
+        CreateCommandOption::new(CommandOptionType::Channel, "channel", "The target channel")
+            .channel_types(vec![ChannelType::Text])
+            .required(true)
+    };
+    let hour = |name: &str, description: &str| {
+        CreateCommandOption::new(CommandOptionType::Integer, name, description)
+            .max_int_value(23)
+            .min_int_value(0)
+    };
+
+    let spawns = CreateCommandOption::new(
+        CommandOptionType::SubCommandGroup,
+        "spawns",
+        "Configures wild Pokémon spawns",
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "add",
+            "Allows spawns in a channel",
+        )
+        .add_sub_option(channel()),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "remove",
+            "Disallows spawns in a channel",
+        )
+        .add_sub_option(channel()),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "rate",
+            "Sets the chance of a spawn on each clock tick",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "percent", "The spawn chance")
+                .max_int_value(100)
+                .min_int_value(0)
+                .required(true),
+        ),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "quiet",
+            "Sets the hours in which spawns are paused, or clears them if omitted",
+        )
+        .add_sub_option(hour("start", "The starting hour (UTC)"))
+        .add_sub_option(hour("end", "The ending hour (UTC)")),
+    )
+    .add_sub_option(CreateCommandOption::new(
+        CommandOptionType::SubCommand,
+        "view",
+        "Displays the current spawn settings",
+    ));
+
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .description("Configures the bot for this guild")
+        .dm_permission(false)
+        .add_option(spawns)
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    command.defer_ephemeral(context).await?;
+
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| anyhow!("this command must be used within a guild"))?;
+    let options = command.data.options();
+    let options = get_subcommand_group(&options, "spawns")?;
+
+    let key = guild_id.to_string();
+    let mut settings = events.guilds.lock(&key).await?;
+
+    if let Ok(options) = get_subcommand(options, "add") {
+        let channel = get_partial_channel(options, "channel")?;
+
+        settings.channels.insert(channel.id);
+    } else if let Ok(options) = get_subcommand(options, "remove") {
+        let channel = get_partial_channel(options, "channel")?;
+
+        settings.channels.remove(&channel.id);
+    } else if let Ok(options) = get_subcommand(options, "rate") {
+        settings.rate = u8::try_from(get_i64(options, "percent")?.clamp(0, 100))?;
+    } else if let Ok(options) = get_subcommand(options, "quiet") {
+        let start = get_i64(options, "start").ok();
+        let end = get_i64(options, "end").ok();
+
+        settings.quiet_hours = match (start, end) {
+            (Some(start), Some(end)) => Some((u8::try_from(start)?, u8::try_from(end)?)),
+            (None, None) => None,
+            _ => return Err(anyhow!("both a start and end hour are required")),
+        };
+    }
+
+    let embed = settings.to_embed(());
+    drop(settings);
+
+    let builder = CreateInteractionResponseFollowup::new().embed(embed);
+    command.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+handler! {
+    create: new,
+    command: command,
+}
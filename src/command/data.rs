@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{handler, prelude::*};
 
 pub const NAME: &str = "data";
 pub const STATEMENT: &str = include_str!("../../include/data/statement.txt");
@@ -10,7 +10,7 @@ pub fn new() -> CreateCommand {
         .dm_permission(true)
 }
 
-pub async fn command(context: &Context, command: &CommandInteraction) -> Result<()> {
+pub async fn command(_: &Events, context: &Context, command: &CommandInteraction) -> Result<()> {
     command.defer_ephemeral(context).await?;
 
     let bot_user = context.http().get_current_user().await?;
@@ -27,3 +27,8 @@ pub async fn command(context: &Context, command: &CommandInteraction) -> Result<
 
     Ok(())
 }
+
+handler! {
+    create: new,
+    command: command,
+}
@@ -0,0 +1,310 @@
+use std::num::NonZeroU64;
+
+use rustemon::model::{
+    evolution::ChainLink,
+    pokemon::{Pokemon, PokemonSpecies},
+};
+
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "dex";
+pub const BAR_WIDTH: i64 = 12;
+pub const MAX_STAT: i64 = 255;
+
+pub fn new() -> CreateCommand {
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Displays Pokédex information for a species")
+        .dm_permission(true)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "species",
+                "The species to look up",
+            )
+            .required(true)
+            .set_autocomplete(true),
+        )
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    command.defer(context).await?;
+
+    let options = command.data.options();
+    let value = get_str(&options, "species")?;
+    let species_id = events.names.resolve(NameKind::Species, value)?.id;
+    let user_id = command.user.id;
+    let entry = DexEntry::fetch(events, user_id, species_id, 0, false).await?;
+
+    let buttons = entry.try_to_buttons(false, user_id)?;
+    let builder = CreateInteractionResponseFollowup::new()
+        .embed(entry.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+    command.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+pub async fn component(
+    events: &Events,
+    context: &Context,
+    component: &ComponentInteraction,
+    custom_id: &CustomId<'_>,
+) -> Result<()> {
+    let species_id = custom_id.data_as(0)?;
+    let mut form: i64 = custom_id.data_as(1)?;
+    let mut shiny = custom_id.data_as(2)?;
+    let user_id = UserId::from(custom_id.data_as::<NonZeroU64>(3)?);
+
+    match custom_id.name {
+        "prev" => form -= 1,
+        "next" => form += 1,
+        "shiny" => shiny = !shiny,
+        _ => return Err(anyhow!("invalid custom identifier name")),
+    }
+
+    let entry = DexEntry::fetch(events, user_id, species_id, form, shiny).await?;
+
+    let buttons = entry.try_to_buttons(false, user_id)?;
+    let message = CreateInteractionResponseMessage::new()
+        .embed(entry.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+
+    let builder = CreateInteractionResponse::UpdateMessage(message);
+    component.create_response(context, builder).await?;
+
+    Ok(())
+}
+
+fn stages(events: &Events, root: &ChainLink) -> Vec<Vec<String>> {
+    let mut stages = vec![];
+    let mut links = vec![root];
+
+    while !links.is_empty() {
+        let names = links.iter().map(|link| {
+            let species = &link.species;
+
+            resource_id(&species.url)
+                .and_then(|id| events.names.get(NameKind::Species, id))
+                .map_or_else(|| title_case(&species.name), |e| e.label.clone())
+        });
+
+        stages.push(names.collect());
+        links = links.into_iter().flat_map(|l| &l.evolves_to).collect();
+    }
+
+    stages
+}
+
+#[derive(Clone, Debug)]
+pub struct DexEntry {
+    pub species: PokemonSpecies,
+    pub pokemon: Pokemon,
+    pub form: usize,
+    pub forms: usize,
+    pub shiny: bool,
+    pub stages: Vec<Vec<String>>,
+    pub caught: usize,
+}
+
+impl DexEntry {
+    pub async fn fetch(
+        events: &Events,
+        user_id: UserId,
+        species_id: i64,
+        form: i64,
+        shiny: bool,
+    ) -> Result<Self> {
+        let pokeapi = &events.pokeapi;
+        let species = pokeapi.species(species_id).await?;
+        let forms = species.varieties.len().max(1);
+        let form = usize::try_from(form.rem_euclid(i64::try_from(forms)?))?;
+
+        let pokemon_id = species
+            .varieties
+            .get(form)
+            .and_then(|v| resource_id(&v.pokemon.url))
+            .unwrap_or(species_id);
+        let pokemon = pokeapi.pokemon(pokemon_id).await?;
+
+        let stages = match resource_id(&species.evolution_chain.url) {
+            Some(id) => {
+                let chain = pokeapi.evolution_chain(id).await?;
+
+                stages(events, &chain.chain)
+            }
+            None => vec![],
+        };
+
+        let key = user_id.to_string();
+        let caught = events
+            .collections
+            .lock(&key)
+            .await?
+            .pokemon
+            .iter()
+            .filter(|c| c.species_id == species_id)
+            .count();
+
+        Ok(Self {
+            species,
+            pokemon,
+            form,
+            forms,
+            shiny,
+            stages,
+            caught,
+        })
+    }
+
+    pub fn types(&self) -> Vec<String> {
+        let mut types = self.pokemon.types.clone();
+        types.sort_by_key(|t| t.slot);
+
+        types
+            .into_iter()
+            .map(|t| title_case(&t.type_.name))
+            .collect()
+    }
+    pub fn abilities(&self) -> Vec<String> {
+        let mut abilities = self.pokemon.abilities.clone();
+        abilities.sort_by_key(|a| a.slot);
+
+        abilities
+            .into_iter()
+            .map(|a| {
+                let hidden = if a.is_hidden { " *(hidden)*" } else { "" };
+
+                format!("{}{hidden}", title_case(&a.ability.name))
+            })
+            .collect()
+    }
+    pub fn flavor_text(&self) -> Option<String> {
+        let entry = self
+            .species
+            .flavor_text_entries
+            .iter()
+            .rev()
+            .find(|f| f.language.name == "en")?;
+
+        Some(
+            entry
+                .flavor_text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+    pub fn stats(&self) -> String {
+        let lines: Vec<_> = self
+            .pokemon
+            .stats
+            .iter()
+            .zip(STAT_NAMES)
+            .map(|(stat, name)| {
+                let value = stat.base_stat;
+                let width = (value * BAR_WIDTH / MAX_STAT).clamp(1, BAR_WIDTH);
+                let bar = "█".repeat(usize::try_from(width).unwrap_or_default());
+
+                format!("{name:<3} {value:>3} {bar}")
+            })
+            .collect();
+
+        format!("```\n{}\n```", lines.join("\n"))
+    }
+}
+
+impl ToEmbed for DexEntry {
+    type Args = ();
+
+    fn to_embed(&self, _: Self::Args) -> CreateEmbed {
+        let star = if self.shiny { " ✨" } else { "" };
+        let color = if self.shiny {
+            Color::GOLD
+        } else {
+            Color::ROSEWATER
+        };
+        let caught = match self.caught {
+            0 => "> Not yet!".to_string(),
+            count => format!("> Yes! ({count} caught)"),
+        };
+        let evolution = if self.stages.len() > 1 {
+            let stages: Vec<_> = self.stages.iter().map(|s| s.join(" / ")).collect();
+
+            format!("> {}", stages.join(" → "))
+        } else {
+            "> Does not evolve".to_string()
+        };
+        let footer = format!(
+            "Form {} of {} • {}",
+            self.form + 1,
+            self.forms,
+            self.pokemon.name
+        );
+
+        CreateEmbed::new()
+            .color(color)
+            .description(self.flavor_text().unwrap_or_default())
+            .field("Types", format!("> {}", self.types().join(", ")), true)
+            .field("Caught", caught, true)
+            .field(
+                "Abilities",
+                format!("> {}", self.abilities().join(", ")),
+                false,
+            )
+            .field("Base Stats", self.stats(), false)
+            .field("Evolution", evolution, false)
+            .footer(CreateEmbedFooter::new(footer))
+            .image(Encounter::artwork_url_of(self.pokemon.id, self.shiny))
+            .title(format!(
+                "{} #{}{star}",
+                english_name(&self.species),
+                self.species.id
+            ))
+    }
+}
+
+impl TryToButtons for DexEntry {
+    type Args = UserId;
+
+    fn try_to_buttons(&self, disabled: bool, user_id: Self::Args) -> Result<Vec<CreateButton>> {
+        let single = self.forms <= 1;
+
+        let custom_id = |name: &'static str| -> Result<String> {
+            let mut custom_id = CustomId::new(NAME, name);
+
+            custom_id.push_data(self.species.id.to_string())?;
+            custom_id.push_data(self.form.to_string())?;
+            custom_id.push_data(self.shiny.to_string())?;
+            custom_id.push_data(user_id.to_string())?;
+
+            Ok(custom_id.to_string())
+        };
+
+        Ok(vec![
+            CreateButton::new(custom_id("prev")?)
+                .disabled(disabled || single)
+                .emoji('◀')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("shiny")?)
+                .disabled(disabled)
+                .emoji('✨')
+                .label(if self.shiny { "Normal" } else { "Shiny" })
+                .style(ButtonStyle::Primary),
+            CreateButton::new(custom_id("next")?)
+                .disabled(disabled || single)
+                .emoji('▶')
+                .style(ButtonStyle::Secondary),
+        ])
+    }
+}
+
+handler! {
+    create: new,
+    command: command,
+    component: component,
+}
@@ -0,0 +1,85 @@
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "export";
+
+pub fn new() -> CreateCommand {
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Sends you a copy of all of your stored data")
+        .dm_permission(true)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "format",
+                "The file format of the export",
+            )
+            .add_string_choice("JSON", "json")
+            .add_string_choice("MessagePack", "rmp"),
+        )
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    command.defer_ephemeral(context).await?;
+
+    let options = command.data.options();
+    let format = get_str(&options, "format").unwrap_or("json");
+    let user = &command.user;
+    let key = user.id.to_string();
+
+    let mut export = BTreeMap::new();
+
+    events.collections.flush().await?;
+    events.progress.flush().await?;
+
+    for dir in Data::<serde_json::Value>::dirs().await? {
+        if !Data::<serde_json::Value>::exists(&dir, &key).await? {
+            continue;
+        }
+
+        let value = Data::<serde_json::Value>::load(&dir, &key).await?;
+
+        export.insert(dir, value.res_unwrap());
+    }
+
+    let bytes = match format {
+        "json" => serde_json::to_vec_pretty(&export)?,
+        "rmp" => rmp_serde::to_vec_named(&export)?,
+        _ => return Err(anyhow!("invalid export format \"{format}\"")),
+    };
+
+    let description = if export.is_empty() {
+        "> Looks like there isn't any data stored about you!".to_string()
+    } else {
+        let entries: Vec<_> = export.keys().map(|dir| format!("> `{dir}`")).collect();
+
+        format!(
+            "__The attached file contains the following data:__\n{}",
+            entries.join("\n")
+        )
+    };
+
+    let author = CreateEmbedAuthor::new(user.tag()).icon_url(user.face());
+    let embed = CreateEmbed::new()
+        .author(author)
+        .color(Color::ROSEWATER)
+        .description(description)
+        .title("Data Export");
+    let file = CreateAttachment::bytes(bytes, format!("{key}.{format}"));
+
+    let builder = CreateInteractionResponseFollowup::new()
+        .add_file(file)
+        .embed(embed)
+        .ephemeral(true);
+    command.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+handler! {
+    create: new,
+    command: command,
+}
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{handler, prelude::*};
 
 pub const NAME: &str = "help";
 pub const HEADER: &str = include_str!("../../include/help/header.txt");
@@ -11,7 +11,7 @@ pub fn new() -> CreateCommand {
         .dm_permission(true)
 }
 
-pub async fn command(context: &Context, command: &CommandInteraction) -> Result<()> {
+pub async fn command(_: &Events, context: &Context, command: &CommandInteraction) -> Result<()> {
     command.defer_ephemeral(context).await?;
 
     let mut description = HEADER.to_string();
@@ -85,3 +85,8 @@ fn stringify(commands: Vec<Command>) -> Vec<String> {
         })
         .collect()
 }
+
+handler! {
+    create: new,
+    command: command,
+}
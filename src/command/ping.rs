@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{handler, prelude::*};
 
 pub const NAME: &str = "ping";
 
@@ -9,7 +9,7 @@ pub fn new() -> CreateCommand {
         .dm_permission(true)
 }
 
-pub async fn command(context: &Context, command: &CommandInteraction) -> Result<()> {
+pub async fn command(_: &Events, context: &Context, command: &CommandInteraction) -> Result<()> {
     command.defer_ephemeral(context).await?;
 
     let bot_user = context.http().get_current_user().await?;
@@ -33,3 +33,8 @@ pub async fn command(context: &Context, command: &CommandInteraction) -> Result<
 
     Ok(())
 }
+
+handler! {
+    create: new,
+    command: command,
+}
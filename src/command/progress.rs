@@ -0,0 +1,296 @@
+use serenity::all::Mentionable;
+
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "progress";
+pub const PAGE_SIZE: usize = 24;
+pub const ROW_SIZE: usize = 3;
+
+pub fn new() -> CreateCommand {
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Displays a user's Pokédex completion")
+        .dm_permission(true)
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::User,
+            "user",
+            "The user whose progress to display",
+        ))
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    command.defer_ephemeral(context).await?;
+
+    let options = command.data.options();
+    let owner = get_user(&options, "user").map_or_else(|_| command.user.clone(), |u| u.0.clone());
+    let view = ProgressView::new(events, owner, 0).await?;
+
+    let buttons = view.try_to_buttons(false, ())?;
+    let builder = CreateInteractionResponseFollowup::new()
+        .embed(view.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+    command.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+pub async fn component(
+    events: &Events,
+    context: &Context,
+    component: &ComponentInteraction,
+    custom_id: &CustomId<'_>,
+) -> Result<()> {
+    let owner = UserId::new(custom_id.data_as(0)?).to_user(context).await?;
+    let page = custom_id.data_as(1)?;
+    let mut view = ProgressView::new(events, owner, page).await?;
+
+    match custom_id.name {
+        "first" => view.page = 0,
+        "prev" => view.page = view.page.saturating_sub(1),
+        "next" => view.page += 1,
+        "last" => view.page = view.pages() - 1,
+        _ => return Err(anyhow!("invalid custom identifier name")),
+    }
+
+    view.page = view.page.min(view.pages() - 1);
+
+    let buttons = view.try_to_buttons(false, ())?;
+    let message = CreateInteractionResponseMessage::new()
+        .embed(view.to_embed(()))
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+
+    let builder = CreateInteractionResponse::UpdateMessage(message);
+    component.create_response(context, builder).await?;
+
+    Ok(())
+}
+
+pub async fn load(events: &Events, key: &str) -> Result<StoreGuard<Progress>> {
+    let mut progress = events.progress.lock(key).await?;
+
+    if progress.caught.is_empty() {
+        let species: Vec<_> = events
+            .collections
+            .lock(key)
+            .await?
+            .pokemon
+            .iter()
+            .map(|c| c.species_id)
+            .collect();
+
+        if !species.is_empty() {
+            progress.seed(species);
+        }
+    }
+
+    Ok(progress)
+}
+
+pub async fn see(events: &Events, channel_id: ChannelId, species_id: i64) -> Result<()> {
+    for user_id in events.presence.active(channel_id)? {
+        load(events, &user_id.to_string()).await?.see(species_id);
+    }
+
+    Ok(())
+}
+
+pub async fn catch(
+    events: &Events,
+    http: &Http,
+    channel_id: ChannelId,
+    user: &User,
+    species_id: i64,
+) -> Result<()> {
+    let key = user.id.to_string();
+    let milestone = load(events, &key).await?.catch(species_id);
+
+    let Some(milestone) = milestone else {
+        return Ok(());
+    };
+
+    let author = CreateEmbedAuthor::new(user.tag()).icon_url(user.face());
+    let embed = CreateEmbed::new()
+        .author(author)
+        .color(Color::GOLD)
+        .description(format!(
+            "{} has caught {milestone}% of all Pokémon!",
+            user.mention()
+        ))
+        .title("Pokédex milestone reached! 🎉");
+
+    let builder = CreateMessage::new().embed(embed);
+    channel_id.send_message(http, builder).await?;
+
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct ProgressView {
+    pub owner: User,
+    pub progress: Progress,
+    pub missing: Vec<String>,
+    pub page: usize,
+}
+
+impl ProgressView {
+    pub async fn new(events: &Events, owner: User, page: usize) -> Result<Self> {
+        let key = owner.id.to_string();
+        let progress = load(events, &key).await?.clone();
+        let missing = progress
+            .missing()
+            .into_iter()
+            .map(|id| {
+                let name = events
+                    .names
+                    .get(NameKind::Species, id)
+                    .map_or("???", |e| e.label.as_str());
+
+                format!("`#{id:04}` {name}")
+            })
+            .collect();
+
+        Ok(Self {
+            owner,
+            progress,
+            missing,
+            page,
+        })
+    }
+
+    pub fn pages(&self) -> usize {
+        self.missing.len().div_ceil(PAGE_SIZE).max(1)
+    }
+}
+
+impl ToEmbed for ProgressView {
+    type Args = ();
+
+    fn to_embed(&self, _: Self::Args) -> CreateEmbed {
+        let Progress { seen, caught, .. } = &self.progress;
+        let total = Encounter::DEX_SIZE;
+
+        let mut description = format!(
+            "**Caught:** {} / {total} ({:.1}%)\n**Seen:** {} / {total} ({:.1}%)\n",
+            caught.len(),
+            percent(caught.len(), total),
+            seen.len(),
+            percent(seen.len(), total),
+        );
+
+        for generation in 1..=Region::GENERATIONS {
+            let regions = Region::ALL
+                .into_iter()
+                .filter(|r| r.generation == generation);
+            let (count, size) = regions.fold((0, 0), |(count, size), region| {
+                (
+                    count + self.progress.caught_in(region),
+                    size + region.size(),
+                )
+            });
+
+            description.push_str(&format!(
+                "\n> Gen {}: {count} / {size} ({:.1}%)",
+                numeral(generation),
+                percent(count, size)
+            ));
+        }
+
+        let grid = self
+            .missing
+            .iter()
+            .skip(self.page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .collect::<Vec<_>>()
+            .chunks(ROW_SIZE)
+            .map(|row| {
+                row.iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" • ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let grid = if grid.is_empty() {
+            "> Gotta catch 'em all... and you did!".to_string()
+        } else {
+            grid
+        };
+
+        let author = CreateEmbedAuthor::new(self.owner.tag()).icon_url(self.owner.face());
+        let footer = format!(
+            "Page {} of {} • {} missing",
+            self.page + 1,
+            self.pages(),
+            self.missing.len()
+        );
+        let mut embed = CreateEmbed::new()
+            .author(author)
+            .color(Color::ROSEWATER)
+            .description(description)
+            .footer(CreateEmbedFooter::new(footer))
+            .title(format!("{}'s Pokédex", self.owner.name));
+
+        for region in Region::ALL {
+            let caught = self.progress.caught_in(region);
+            let seen = self.progress.seen_in(region);
+
+            embed = embed.field(
+                format!("{} (Gen {})", region.name, numeral(region.generation)),
+                format!(
+                    "> {caught} / {} caught ({:.1}%)\n> {seen} seen",
+                    region.size(),
+                    percent(caught, region.size())
+                ),
+                true,
+            );
+        }
+
+        embed.field("Missing", grid, false)
+    }
+}
+
+impl TryToButtons for ProgressView {
+    type Args = ();
+
+    fn try_to_buttons(&self, disabled: bool, _: Self::Args) -> Result<Vec<CreateButton>> {
+        let last = self.page + 1 >= self.pages();
+
+        let custom_id = |name: &'static str| -> Result<String> {
+            let mut custom_id = CustomId::new(NAME, name);
+
+            custom_id.push_data(self.owner.id.to_string())?;
+            custom_id.push_data(self.page.to_string())?;
+
+            Ok(custom_id.to_string())
+        };
+
+        Ok(vec![
+            CreateButton::new(custom_id("first")?)
+                .disabled(disabled || self.page == 0)
+                .emoji('⏮')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("prev")?)
+                .disabled(disabled || self.page == 0)
+                .emoji('◀')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("next")?)
+                .disabled(disabled || last)
+                .emoji('▶')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(custom_id("last")?)
+                .disabled(disabled || last)
+                .emoji('⏭')
+                .style(ButtonStyle::Secondary),
+        ])
+    }
+}
+
+handler! {
+    create: new,
+    command: command,
+    component: component,
+}
@@ -0,0 +1,124 @@
+use serenity::all::InputTextStyle;
+
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "purge";
+pub const INPUT: &str = "phrase";
+pub const PHRASE: &str = "DELETE";
+
+pub fn new() -> CreateCommand {
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Permanently deletes all of your stored data")
+        .dm_permission(true)
+}
+
+pub async fn command(_: &Events, context: &Context, command: &CommandInteraction) -> Result<()> {
+    let builder = CreateInteractionResponse::Modal(Confirmation.to_modal(()));
+    command.create_response(context, builder).await?;
+
+    Ok(())
+}
+
+pub async fn modal(
+    events: &Events,
+    context: &Context,
+    modal: &ModalInteraction,
+    custom_id: &CustomId<'_>,
+) -> Result<()> {
+    match custom_id.name {
+        "confirm" => confirm(events, context, modal).await,
+        _ => Err(anyhow!("invalid custom identifier name")),
+    }
+}
+
+async fn confirm(events: &Events, context: &Context, modal: &ModalInteraction) -> Result<()> {
+    modal.defer_ephemeral(context).await?;
+
+    let phrase = get_input_text(&modal.data.components, INPUT)?;
+    let user = &modal.user;
+    let author = CreateEmbedAuthor::new(user.tag()).icon_url(user.face());
+
+    if phrase.trim() != PHRASE {
+        let embed = CreateEmbed::new()
+            .author(author)
+            .color(Color::ROSEWATER)
+            .description("The confirmation phrase did not match, so nothing was deleted.")
+            .title("Purge cancelled");
+
+        let builder = CreateInteractionResponseFollowup::new().embed(embed);
+        modal.create_followup(context, builder).await?;
+
+        return Ok(());
+    }
+
+    let key = user.id.to_string();
+    let mut removed = vec![];
+
+    if events.collections.delete(&key).await? {
+        removed.push(format!("> `{}`", Collection::DIR));
+    }
+    if events.progress.delete(&key).await? {
+        removed.push(format!("> `{}`", Progress::DIR));
+    }
+
+    for dir in Data::<serde_json::Value>::dirs().await? {
+        if dir == Collection::DIR
+            || dir == Progress::DIR
+            || !Data::<serde_json::Value>::exists(&dir, &key).await?
+        {
+            continue;
+        }
+
+        Data::new(&dir, &key, serde_json::Value::Null)
+            .res_delete()
+            .await?;
+        removed.push(format!("> `{dir}`"));
+    }
+
+    let description = if removed.is_empty() {
+        "> Looks like there wasn't any data to delete!".to_string()
+    } else {
+        format!(
+            "__The following data was deleted:__\n{}",
+            removed.join("\n")
+        )
+    };
+    let embed = CreateEmbed::new()
+        .author(author)
+        .color(Color::ROSEWATER)
+        .description(description)
+        .title("Purge complete");
+
+    let builder = CreateInteractionResponseFollowup::new().embed(embed);
+    modal.create_followup(context, builder).await?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Confirmation;
+
+impl ToModal for Confirmation {
+    type Args = ();
+
+    fn to_modal(&self, _: Self::Args) -> CreateModal {
+        let custom_id = CustomId::new(NAME, "confirm");
+        let input = CreateInputText::new(
+            InputTextStyle::Short,
+            format!("Type \"{PHRASE}\" to confirm"),
+            INPUT,
+        )
+        .placeholder(PHRASE)
+        .required(true);
+
+        CreateModal::new(custom_id.to_string(), "Delete all of your data?")
+            .components(vec![CreateActionRow::InputText(input)])
+    }
+}
+
+handler! {
+    create: new,
+    command: command,
+    modal: modal,
+}
@@ -0,0 +1,89 @@
+use crate::{handler, prelude::*};
+
+pub const NAME: &str = "roll";
+pub const COOLDOWN: i64 = 600;
+
+pub fn new() -> CreateCommand {
+    CreateCommand::new(NAME)
+        .default_member_permissions(Permissions::USE_APPLICATION_COMMANDS)
+        .description("Rolls a random Pokémon")
+        .dm_permission(true)
+}
+
+pub async fn command(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<()> {
+    let user = &command.user;
+    let duration = chrono::Duration::seconds(COOLDOWN);
+
+    if let Some(until) = events.cooldowns.try_start(NAME, user.id, duration)? {
+        let embed = CreateEmbed::new()
+            .color(Color::RED)
+            .description(format!("You can roll again {}", Timestamp::from(until)))
+            .title("You're on cooldown!");
+        let message = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true);
+
+        let builder = CreateInteractionResponse::Message(message);
+        command.create_response(context, builder).await?;
+
+        return Ok(());
+    }
+
+    let (message, encounter) = match roll(events, context, command).await {
+        Ok(rolled) => rolled,
+        Err(error) => {
+            events.cooldowns.clear(NAME, user.id)?;
+
+            return Err(error);
+        }
+    };
+
+    events.cooldowns.save().await?;
+
+    let species_id = encounter.species_id;
+    progress::catch(events, context.http(), message.channel_id, user, species_id).await?;
+
+    Ok(())
+}
+
+async fn roll(
+    events: &Events,
+    context: &Context,
+    command: &CommandInteraction,
+) -> Result<(Message, Encounter)> {
+    command.defer(context).await?;
+
+    let user = &command.user;
+    let mut rng = StdRng::from_entropy();
+    let encounter = events.table.roll(&*events.rarities, &mut rng).await?;
+    let info = EncounterInfo::fetch(&events.pokeapi, encounter).await?;
+    let author = CreateEmbedAuthor::new(user.tag()).icon_url(user.face());
+    let embed = info.to_embed(()).author(author).description(format!(
+        "You rolled a level {} **{}**!",
+        encounter.level,
+        info.name()
+    ));
+
+    let builder = CreateInteractionResponseFollowup::new().embed(embed);
+    let message = command.create_followup(context, builder).await?;
+
+    let anchor = Anchor::new(command.guild_id, message.channel_id, message.id);
+    let key = user.id.to_string();
+
+    events
+        .collections
+        .lock(&key)
+        .await?
+        .push(Caught::new(&info, Some(anchor)));
+
+    Ok((message, encounter))
+}
+
+handler! {
+    create: new,
+    command: command,
+}
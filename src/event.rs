@@ -1,23 +1,72 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use serenity::{
     all::{OnlineStatus, Ready},
     gateway::ActivityData,
 };
 
-use crate::{dev_guild, error, info, prelude::*, DEV_BUILD};
+use crate::{
+    dev_guild, error, info,
+    prelude::*,
+    spawn::{self, Spawns},
+    DEV_BUILD,
+};
 
+#[derive(Clone)]
 pub struct Events {
     pub logger: Logger,
-    pub pokeapi: RustemonClient,
+    pub pokeapi: Arc<PokeApi>,
+    pub rarities: Arc<RarityCache<Arc<PokeApi>>>,
+    pub table: Arc<RollTable>,
+    pub names: Arc<NameIndex>,
+    pub cooldowns: Cooldowns,
+    pub spawns: Spawns,
+    pub collections: Store<Collection>,
+    pub progress: Store<Progress>,
+    pub guilds: Store<GuildSettings>,
+    pub presence: Presence,
+    restoring: Arc<AtomicBool>,
+    restored: Arc<AtomicBool>,
 }
 
 impl Events {
-    pub const fn new(logger: Logger, pokeapi: RustemonClient) -> Self {
-        Self { logger, pokeapi }
+    pub fn new(
+        storage: &'static dyn Storage,
+        logger: Logger,
+        pokeapi: Arc<PokeApi>,
+        table: Arc<RollTable>,
+        names: Arc<NameIndex>,
+    ) -> Self {
+        let cooldowns = Cooldowns::default();
+        let spawns = Spawns::default();
+        let rarities = Arc::new(RarityCache::new(Arc::clone(&pokeapi)));
+        let collections = Store::new(storage, Collection::DIR);
+        let progress = Store::new(storage, Progress::DIR);
+        let guilds = Store::new(storage, GuildSettings::DIR);
+        let presence = Presence::default();
+        let restoring = Arc::default();
+        let restored = Arc::default();
+
+        Self {
+            logger,
+            pokeapi,
+            rarities,
+            table,
+            names,
+            cooldowns,
+            spawns,
+            collections,
+            progress,
+            guilds,
+            presence,
+            restoring,
+            restored,
+        }
     }
 
     pub async fn create_commands(&self, http: &Http) -> Result<()> {
         let guild_id = dev_guild()?;
-        let cmds = vec![data::new(), help::new(), ping::new()];
+        let cmds: Vec<_> = COMMANDS.iter().filter_map(|c| c.create()).collect();
 
         let global = if DEV_BUILD {
             http.get_global_application_commands().await?.len()
@@ -33,6 +82,52 @@ impl Events {
 
         Ok(())
     }
+    pub fn is_restored(&self) -> bool {
+        self.restored.load(Ordering::Acquire)
+    }
+    pub async fn restore(&self, http: &Http) -> Result<()> {
+        let cooldowns = self.cooldowns.restore().await?;
+
+        info!(self.logger, "Restored {cooldowns} cooldowns");
+
+        let mut restored = 0;
+
+        for spawn in Spawns::load().await? {
+            let result = if spawn.is_expired() {
+                spawn::flee(http, &spawn).await
+            } else {
+                match spawn::set_disabled(http, &spawn, false).await {
+                    Ok(()) => self.spawns.insert(spawn).map(|()| restored += 1),
+                    Err(error) => Err(error),
+                }
+            };
+
+            if let Err(error) = result {
+                let time = Local::now();
+
+                error!(self.logger, time, "Spawn could not be restored: {error}");
+            }
+        }
+
+        self.spawns.save().await?;
+
+        info!(self.logger, "Restored {restored} spawns");
+
+        Ok(())
+    }
+    async fn record_presence(&self, guild_id: GuildId, message: &Message) -> Result<()> {
+        let key = guild_id.to_string();
+        let settings = self.guilds.lock(&key).await?;
+        let configured = settings.channels.contains(&message.channel_id);
+
+        drop(settings);
+
+        if !configured {
+            return Ok(());
+        }
+
+        self.presence.record(message.channel_id, message.author.id)
+    }
 }
 
 #[async_trait]
@@ -51,9 +146,39 @@ impl EventHandler for Events {
 
             error!(self.logger, time, "Error creating commands: {error}");
         }
+
+        let claimed =
+            self.restoring
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire);
+
+        if claimed.is_err() {
+            return;
+        }
+
+        if let Err(error) = self.restore(context.http()).await {
+            let time = Local::now();
+
+            error!(self.logger, time, "Error restoring state: {error}");
+        }
+
+        self.restored.store(true, Ordering::Release);
     }
 
-    // async fn message(&self, context: Context, message: Message) {}
+    async fn message(&self, _: Context, message: Message) {
+        if message.author.bot {
+            return;
+        }
+
+        let Some(guild_id) = message.guild_id else {
+            return;
+        };
+
+        if let Err(error) = self.record_presence(guild_id, &message).await {
+            let time = Local::now();
+
+            error!(self.logger, time, "Error recording presence: {error}");
+        }
+    }
 
     #[allow(clippy::match_wildcard_for_single_variants)]
     async fn interaction_create(&self, context: Context, mut interaction: Interaction) {
@@ -67,11 +192,35 @@ impl EventHandler for Events {
         };
 
         let result: Result<()> = match &mut interaction {
-            Interaction::Command(command) => match command.data.name.as_str() {
-                data::NAME => data::command(&context, command).await,
-                help::NAME => help::command(&context, command).await,
-                ping::NAME => ping::command(&context, command).await,
-                _ => Err(anyhow!("unknown interaction: {id}")),
+            Interaction::Autocomplete(autocomplete) => {
+                match find_command(&autocomplete.data.name) {
+                    Some(handler) => handler.autocomplete(self, &context, autocomplete).await,
+                    None => Err(anyhow!("unknown interaction: {id}")),
+                }
+            }
+            Interaction::Command(command) => match find_command(&command.data.name) {
+                Some(handler) => handler.command(self, &context, command).await,
+                None => Err(anyhow!("unknown interaction: {id}")),
+            },
+            Interaction::Component(component) => {
+                match CustomId::try_resolve(&component.data.custom_id) {
+                    Ok(custom_id) => match find_command(custom_id.base) {
+                        Some(handler) => {
+                            handler
+                                .component(self, &context, component, &custom_id)
+                                .await
+                        }
+                        None => Err(anyhow!("unknown interaction: {id}")),
+                    },
+                    Err(error) => Err(error),
+                }
+            }
+            Interaction::Modal(modal) => match CustomId::try_resolve(&modal.data.custom_id) {
+                Ok(custom_id) => match find_command(custom_id.base) {
+                    Some(handler) => handler.modal(self, &context, modal, &custom_id).await,
+                    None => Err(anyhow!("unknown interaction: {id}")),
+                },
+                Err(error) => Err(error),
             },
             _ => Err(anyhow!("unknown interaction: {id}")),
         };
@@ -86,23 +235,31 @@ impl EventHandler for Events {
                 .color(Color::RED)
                 .description(format!("> {error}\n\nError code: `{code}`"))
                 .title("An error occurred!");
-            let response = CreateInteractionResponseFollowup::new()
+            let message = CreateInteractionResponseMessage::new()
+                .embed(embed.clone())
+                .ephemeral(true);
+            let response = CreateInteractionResponse::Message(message);
+            let followup = CreateInteractionResponseFollowup::new()
                 .embed(embed)
                 .ephemeral(true);
 
+            macro_rules! respond {
+                ($interaction:expr) => {
+                    match $interaction.create_response(http, response).await {
+                        Ok(()) => Ok(()),
+                        Err(_) => $interaction
+                            .create_followup(http, followup)
+                            .await
+                            .map(|_| ())
+                            .map_err(Into::into),
+                    }
+                };
+            }
+
             let result = match &interaction {
-                Interaction::Autocomplete(i) => {
-                    i.create_followup(http, response).await.map_err(Into::into)
-                }
-                Interaction::Command(i) => {
-                    i.create_followup(http, response).await.map_err(Into::into)
-                }
-                Interaction::Component(i) => {
-                    i.create_followup(http, response).await.map_err(Into::into)
-                }
-                Interaction::Modal(i) => {
-                    i.create_followup(http, response).await.map_err(Into::into)
-                }
+                Interaction::Command(i) => respond!(i),
+                Interaction::Component(i) => respond!(i),
+                Interaction::Modal(i) => respond!(i),
                 i => Err(anyhow!("invalid interaction type: {:?}", i.kind())),
             };
 
@@ -11,12 +11,16 @@
 #![allow(clippy::multiple_crate_versions, clippy::unused_async)]
 #![allow(clippy::module_name_repetitions)]
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use prelude::*;
+use serenity::all::ShardManager;
+use tokio::{sync::watch, task::JoinHandle};
 
 mod command;
 mod event;
+mod model;
 mod prelude;
+mod spawn;
 mod util;
 
 pub const DEV_BUILD: bool = cfg!(debug_assertions);
@@ -35,6 +39,41 @@ struct Args {
     /// The number of seconds between clock ticks
     #[arg(default_value = "10", long, short)]
     pub clock: u64,
+    /// The number of backup generations kept for each stored record
+    #[arg(default_value = "2", long, short)]
+    pub backups: usize,
+    /// The backend used to store records
+    #[arg(default_value = "file", long, short, value_enum)]
+    pub storage: Backend,
+    /// The format used to write file records, `rmp` if unset; SQLite always stores JSON
+    #[arg(long, short, value_enum)]
+    pub format: Option<Format>,
+    /// Overrides the file format for a single directory, such as `users=json`
+    #[arg(long, short, value_parser = dir_format)]
+    pub dir_format: Vec<(String, Format)>,
+    /// Reads PokeAPI data from the local snapshot instead of the network
+    #[arg(long, short)]
+    pub offline: bool,
+    /// A maintenance task to run instead of starting the bot
+    #[command(subcommand)]
+    pub action: Option<Action>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Action {
+    /// Copies every file record into the SQLite database
+    Migrate,
+    /// Downloads species, Pokémon, type and move data into a local snapshot
+    Snapshot,
+}
+
+fn dir_format(value: &str) -> Result<(String, Format)> {
+    let Some((dir, ext)) = value.split_once('=') else {
+        return Err(anyhow!("expected \"<dir>=<format>\""));
+    };
+    let format = Format::from_ext(ext).ok_or_else(|| anyhow!("invalid format \"{ext}\""))?;
+
+    Ok((dir.to_string(), format))
 }
 
 fn token() -> Result<String> {
@@ -44,15 +83,149 @@ fn dev_guild() -> Result<GuildId> {
     Ok(GuildId::new(std::env::var("DEV_GUILD")?.parse()?))
 }
 
-async fn timer(logger: Logger, clock: u64, token: String) -> ! {
+async fn timer(events: Events, clock: u64, token: String, mut stop: watch::Receiver<bool>) {
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(clock));
-    let http = std::sync::Arc::new(Http::new(&token));
+    let mut rng = StdRng::from_entropy();
+    let http = Arc::new(Http::new(&token));
+    let logger = &events.logger;
 
     info!(logger, "Timer started ({clock} secs)");
 
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = stop.changed() => break,
+        }
+
+        if !events.is_restored() {
+            continue;
+        }
+
+        if let Err(error) = events.collections.flush().await {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing collections: {error}");
+        }
+        if let Err(error) = events.progress.flush().await {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing progress: {error}");
+        }
+        if let Err(error) = events.guilds.flush().await {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing guild settings: {error}");
+        }
+        if let Err(error) = events.presence.prune() {
+            let time = Local::now();
+
+            error!(logger, time, "Error pruning presence: {error}");
+        }
+
+        match spawn::tick(&events, &http, &mut rng).await {
+            Ok(0) => {}
+            Ok(count) => info!(logger, "Spawned {count} Pokémon"),
+            Err(error) => {
+                let time = Local::now();
+
+                error!(logger, time, "Error spawning Pokémon: {error}");
+            }
+        }
+    }
+
+    info!(logger, "Timer stopped");
+}
+
+async fn signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result?,
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await?;
+
+    Ok(())
+}
+
+async fn shutdown(
+    events: Events,
+    http: Arc<Http>,
+    shard_manager: Arc<ShardManager>,
+    stop: watch::Sender<bool>,
+    timer: JoinHandle<()>,
+) {
+    let logger = &events.logger;
+
+    if let Err(error) = signal().await {
+        let time = Local::now();
+
+        error!(
+            logger,
+            time, "Error listening for shutdown signals: {error}"
+        );
+        return;
+    }
+
+    info!(logger, "Shutting down...");
+
+    if stop.send(true).is_ok() {
+        timer.await.ok();
+    }
+
+    match events.spawns.all() {
+        Ok(spawns) => {
+            for spawn in spawns {
+                if let Err(error) = spawn::set_disabled(&http, &spawn, true).await {
+                    let time = Local::now();
+
+                    error!(logger, time, "Spawn could not be closed: {error}");
+                }
+            }
+        }
+        Err(error) => {
+            let time = Local::now();
+
+            error!(logger, time, "Error closing spawns: {error}");
+        }
+    }
+
+    shard_manager.shutdown_all().await;
+
+    match events.collections.flush().await {
+        Ok(count) => info!(logger, "Flushed {count} collections"),
+        Err(error) => {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing collections: {error}");
+        }
+    }
+
+    match events.progress.flush().await {
+        Ok(count) => info!(logger, "Flushed {count} progress records"),
+        Err(error) => {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing progress: {error}");
+        }
     }
+
+    match events.guilds.flush().await {
+        Ok(count) => info!(logger, "Flushed {count} guild settings"),
+        Err(error) => {
+            let time = Local::now();
+
+            error!(logger, time, "Error flushing guild settings: {error}");
+        }
+    }
+
+    info!(logger, "Shutdown complete");
 }
 
 #[tokio::main]
@@ -63,19 +236,76 @@ async fn main() -> Result<()> {
         quiet,
         ephemeral,
         clock,
+        backups,
+        storage: backend,
+        format,
+        dir_format,
+        offline,
+        action,
     } = Args::try_parse()?;
 
-    let token = token()?;
     let logger = Logger::new(quiet, ephemeral)?;
-    let pokeapi = RustemonClient::new(CacheMode::Default, Some(CacheOptions::default()));
+
+    if let Some(Action::Migrate) = action {
+        let from = FileStorage::new(backups);
+        let count = copy_records(&from, &SqliteStorage::open()?).await?;
+
+        info!(logger, "Migrated {count} records into SQLite");
+
+        return Ok(());
+    }
+
+    if backend == Backend::Sqlite && (format.is_some() || !dir_format.is_empty()) {
+        return Err(anyhow!(
+            "record formats cannot be chosen for SQLite storage"
+        ));
+    }
+
+    set_storage(backend.open(backups)?)?;
+    set_formats(Formats::new(format.unwrap_or_default(), dir_format))?;
+
+    let pokeapi = Arc::new(if offline {
+        PokeApi::offline(backend.open(backups)?)
+    } else {
+        PokeApi::online()
+    });
+
+    if let Some(Action::Snapshot) = action {
+        let count = pokeapi.snapshot(&logger).await?;
+
+        info!(logger, "Saved {count} records into the snapshot");
+
+        return Ok(());
+    }
+
+    let token = token()?;
 
     info!(logger, "Starting...");
 
-    let event_handler = Events::new(logger.clone(), pokeapi);
+    let table = Arc::new(RollTable::load().await?);
+
+    let names = match pokeapi.names().await {
+        Ok(names) => names,
+        Err(error) => {
+            let time = Local::now();
+
+            error!(logger, time, "Error loading name index: {error}");
+            NameIndex::default()
+        }
+    };
+
+    let event_handler = Events::new(storage()?, logger, pokeapi, table, Arc::new(names));
     let mut client = Client::builder(&token, INTENTS)
-        .event_handler(event_handler)
+        .event_handler(event_handler.clone())
         .await?;
 
-    tokio::spawn(timer(logger, clock, token.clone()));
-    client.start_autosharded().await.map_err(Into::into)
+    let (stop, stopped) = watch::channel(false);
+    let timer = tokio::spawn(timer(event_handler.clone(), clock, token.clone(), stopped));
+    let http = client.http.clone();
+    let shard_manager = client.shard_manager.clone();
+
+    let shutdown = tokio::spawn(shutdown(event_handler, http, shard_manager, stop, timer));
+
+    client.start_autosharded().await?;
+    shutdown.await.map_err(Into::into)
 }
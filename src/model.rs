@@ -0,0 +1,6 @@
+pub mod collection;
+pub mod encounter;
+pub mod guild;
+pub mod names;
+pub mod progress;
+pub mod roll;
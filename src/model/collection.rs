@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub pokemon: Vec<Caught>,
+}
+
+impl Collection {
+    pub const DIR: &str = "users";
+
+    pub fn push(&mut self, caught: Caught) {
+        self.pokemon.push(caught);
+    }
+}
+
+impl Versioned for Collection {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Caught {
+    pub species_id: i64,
+    pub name: String,
+    pub types: Vec<String>,
+    pub nickname: Option<String>,
+    pub level: u8,
+    pub shiny: bool,
+    pub ivs: [u8; 6],
+    pub caught_at: DateTime<Utc>,
+    pub anchor: Option<Anchor>,
+}
+
+impl Caught {
+    pub fn new(info: &EncounterInfo, anchor: Option<Anchor>) -> Self {
+        let Encounter {
+            species_id,
+            level,
+            shiny,
+            ivs,
+        } = info.encounter;
+
+        Self {
+            species_id,
+            name: info.name(),
+            types: info.types(),
+            nickname: None,
+            level,
+            shiny,
+            ivs,
+            caught_at: Utc::now(),
+            anchor,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.name)
+    }
+}
@@ -0,0 +1,125 @@
+use rustemon::model::pokemon::{Pokemon, PokemonSpecies};
+
+use crate::prelude::*;
+
+pub const STAT_NAMES: [&str; 6] = ["HP", "Atk", "Def", "SpA", "SpD", "Spe"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Encounter {
+    pub species_id: i64,
+    pub level: u8,
+    pub shiny: bool,
+    pub ivs: [u8; 6],
+}
+
+impl Encounter {
+    pub const DEX_SIZE: i64 = 1008;
+    pub const MAX_LEVEL: u8 = 100;
+    pub const MAX_IV: u8 = 31;
+
+    const SPRITE_URL: &str =
+        "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon";
+
+    pub fn roll_for(rng: &mut impl Rng, species_id: i64, shiny_chance: f64) -> Self {
+        let level = rng.gen_range(1..=Self::MAX_LEVEL);
+        let shiny = rng.gen_bool(shiny_chance.clamp(0.0, 1.0));
+        let ivs = [(); 6].map(|_| rng.gen_range(0..=Self::MAX_IV));
+
+        Self {
+            species_id,
+            level,
+            shiny,
+            ivs,
+        }
+    }
+
+    pub fn sprite_url(&self) -> String {
+        let shiny = if self.shiny { "/shiny" } else { "" };
+
+        format!("{}{shiny}/{}.png", Self::SPRITE_URL, self.species_id)
+    }
+    pub fn artwork_url_of(pokemon_id: i64, shiny: bool) -> String {
+        let shiny = if shiny { "/shiny" } else { "" };
+
+        format!(
+            "{}/other/official-artwork{shiny}/{pokemon_id}.png",
+            Self::SPRITE_URL
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EncounterInfo {
+    pub encounter: Encounter,
+    pub species: PokemonSpecies,
+    pub pokemon: Pokemon,
+}
+
+impl EncounterInfo {
+    pub async fn fetch(pokeapi: &PokeApi, encounter: Encounter) -> Result<Self> {
+        let id = encounter.species_id;
+        let species = pokeapi.species(id).await?;
+        let pokemon = pokeapi.pokemon(id).await?;
+
+        Ok(Self {
+            encounter,
+            species,
+            pokemon,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        english_name(&self.species)
+    }
+    pub const fn rarity(&self) -> Rarity {
+        Rarity::of(&self.species)
+    }
+    pub fn types(&self) -> Vec<String> {
+        let mut types = self.pokemon.types.clone();
+        types.sort_by_key(|t| t.slot);
+
+        types.into_iter().map(|t| t.type_.name).collect()
+    }
+}
+
+impl ToEmbed for EncounterInfo {
+    type Args = ();
+
+    fn to_embed(&self, _: Self::Args) -> CreateEmbed {
+        let Encounter {
+            species_id,
+            level,
+            shiny,
+            ivs,
+        } = self.encounter;
+
+        let star = if shiny { " ✨" } else { "" };
+        let color = if shiny { Color::GOLD } else { Color::ROSEWATER };
+        let types = self.types().join(", ");
+        let stats = self
+            .pokemon
+            .stats
+            .iter()
+            .zip(STAT_NAMES.into_iter().zip(ivs))
+            .map(|(stat, (name, iv))| format!("`{name:<3}` {} (+{iv})", stat.base_stat))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        CreateEmbed::new()
+            .color(color)
+            .field("Types", format!("> {types}"), true)
+            .field("Level", format!("> {level}"), true)
+            .field("Rarity", format!("> {}", self.rarity().label()), true)
+            .field("Stats", stats, false)
+            .thumbnail(self.encounter.sprite_url())
+            .title(format!("{} #{species_id}{star}", self.name()))
+    }
+}
+
+pub fn english_name(species: &PokemonSpecies) -> String {
+    species
+        .names
+        .iter()
+        .find(|n| n.language.name == "en")
+        .map_or_else(|| species.name.clone(), |n| n.name.clone())
+}
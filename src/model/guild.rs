@@ -0,0 +1,70 @@
+use crate::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildSettings {
+    pub channels: BTreeSet<ChannelId>,
+    pub rate: u8,
+    pub quiet_hours: Option<(u8, u8)>,
+}
+
+impl GuildSettings {
+    pub const DIR: &str = "guilds";
+    pub const DEFAULT_RATE: u8 = 5;
+
+    pub fn chance(&self) -> f64 {
+        f64::from(self.rate.min(100)) / 100.0
+    }
+    pub fn is_quiet(&self, time: DateTime<Utc>) -> bool {
+        let Some((start, end)) = self.quiet_hours else {
+            return false;
+        };
+
+        let hour = time.hour();
+        let (start, end) = (u32::from(start), u32::from(end));
+
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+}
+
+impl Versioned for GuildSettings {}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            channels: BTreeSet::new(),
+            rate: Self::DEFAULT_RATE,
+            quiet_hours: None,
+        }
+    }
+}
+
+impl ToEmbed for GuildSettings {
+    type Args = ();
+
+    fn to_embed(&self, _: Self::Args) -> CreateEmbed {
+        let channels = if self.channels.is_empty() {
+            "> No channels; spawns are disabled".to_string()
+        } else {
+            self.channels
+                .iter()
+                .map(|id| format!("> <#{id}>"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let quiet = self.quiet_hours.map_or_else(
+            || "> None".to_string(),
+            |(start, end)| format!("> {start:02}:00 - {end:02}:00 UTC"),
+        );
+
+        CreateEmbed::new()
+            .color(Color::ROSEWATER)
+            .field("Channels", channels, false)
+            .field("Spawn Rate", format!("> {}% per tick", self.rate), true)
+            .field("Quiet Hours", quiet, true)
+            .title("Spawn Settings")
+    }
+}
@@ -0,0 +1,181 @@
+use rustemon::{
+    items::item, model::resource::NamedApiResource, moves::move_, pokemon::pokemon_species,
+};
+
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum NameKind {
+    Species,
+    Move,
+    Item,
+}
+
+impl NameKind {
+    pub fn from_option(name: &str) -> Option<Self> {
+        match name {
+            "species" | "pokemon" => Some(Self::Species),
+            "move" => Some(Self::Move),
+            "item" => Some(Self::Item),
+            _ => None,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Species => "species",
+            Self::Move => "move",
+            Self::Item => "item",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameEntry {
+    pub id: i64,
+    pub slug: String,
+    pub label: String,
+}
+
+impl NameEntry {
+    pub fn from_resource<T>(resource: &NamedApiResource<T>) -> Option<Self> {
+        Some(Self {
+            id: resource_id(&resource.url)?,
+            slug: resource.name.clone(),
+            label: title_case(&resource.name),
+        })
+    }
+
+    fn score(&self, query: &str) -> Option<usize> {
+        if query.is_empty() || self.slug == query || self.id.to_string() == query {
+            return Some(0);
+        }
+        if self.slug.starts_with(query) {
+            return Some(1);
+        }
+        if self.slug.split('-').any(|word| word.starts_with(query)) {
+            return Some(2);
+        }
+        if self.slug.contains(query) {
+            return Some(3);
+        }
+
+        let mut gaps = 0;
+        let mut slug = self.slug.chars();
+
+        for c in query.chars() {
+            loop {
+                match slug.next() {
+                    Some(next) if next == c => break,
+                    Some(_) => gaps += 1,
+                    None => return None,
+                }
+            }
+        }
+
+        Some(4 + gaps)
+    }
+}
+
+pub fn resource_id(url: &str) -> Option<i64> {
+    url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+pub fn title_case(slug: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NameIndex {
+    entries: BTreeMap<NameKind, Vec<NameEntry>>,
+}
+
+impl NameIndex {
+    pub const LIMIT: i64 = 10_000;
+    pub const MAX_CHOICES: usize = 25;
+
+    pub async fn fetch(pokeapi: &RustemonClient) -> Result<Self> {
+        let species = pokemon_species::get_page_with_param(0, Self::LIMIT, pokeapi).await?;
+        let moves = move_::get_page_with_param(0, Self::LIMIT, pokeapi).await?;
+        let items = item::get_page_with_param(0, Self::LIMIT, pokeapi).await?;
+
+        let mut entries = BTreeMap::new();
+
+        entries.insert(NameKind::Species, Self::collect(&species.results));
+        entries.insert(NameKind::Move, Self::collect(&moves.results));
+        entries.insert(NameKind::Item, Self::collect(&items.results));
+
+        Ok(Self { entries })
+    }
+    fn collect<T>(resources: &[NamedApiResource<T>]) -> Vec<NameEntry> {
+        let mut entries: Vec<_> = resources
+            .iter()
+            .filter_map(NameEntry::from_resource)
+            .collect();
+
+        entries.sort_by_key(|entry| entry.id);
+        entries
+    }
+
+    pub fn entries(&self, kind: NameKind) -> &[NameEntry] {
+        self.entries.get(&kind).map_or(&[], Vec::as_slice)
+    }
+    pub fn get(&self, kind: NameKind, id: i64) -> Option<&NameEntry> {
+        let entries = self.entries.get(&kind)?;
+
+        entries
+            .binary_search_by_key(&id, |entry| entry.id)
+            .ok()
+            .and_then(|index| entries.get(index))
+    }
+    pub fn search(&self, kind: NameKind, query: &str) -> Vec<&NameEntry> {
+        let query: String = query
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+
+        let mut matches: Vec<_> = self
+            .entries
+            .get(&kind)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.score(&query).map(|score| (score, entry)))
+            .collect();
+
+        if !query.is_empty() {
+            matches.sort_by_key(|(score, entry)| (*score, entry.slug.len(), entry.id));
+        }
+
+        matches
+            .into_iter()
+            .take(Self::MAX_CHOICES)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+    pub fn resolve(&self, kind: NameKind, value: &str) -> Result<&NameEntry> {
+        let entry = value
+            .parse()
+            .ok()
+            .and_then(|id| self.get(kind, id))
+            .or_else(|| self.search(kind, value).first().copied());
+
+        entry.ok_or_else(|| anyhow!("unknown {} \"{value}\"", kind.label()))
+    }
+}
+
+impl Versioned for NameIndex {}
@@ -0,0 +1,126 @@
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub generation: u8,
+    pub name: &'static str,
+    pub first: i64,
+    pub last: i64,
+}
+
+impl Region {
+    pub const ALL: [Self; 10] = [
+        Self::new(1, "Kanto", 1, 151),
+        Self::new(2, "Johto", 152, 251),
+        Self::new(3, "Hoenn", 252, 386),
+        Self::new(4, "Sinnoh", 387, 493),
+        Self::new(5, "Unova", 494, 649),
+        Self::new(6, "Kalos", 650, 721),
+        Self::new(7, "Alola", 722, 809),
+        Self::new(8, "Galar", 810, 898),
+        Self::new(8, "Hisui", 899, 905),
+        Self::new(9, "Paldea", 906, Encounter::DEX_SIZE),
+    ];
+    pub const GENERATIONS: u8 = 9;
+
+    pub const fn new(generation: u8, name: &'static str, first: i64, last: i64) -> Self {
+        Self {
+            generation,
+            name,
+            first,
+            last,
+        }
+    }
+
+    pub const fn size(self) -> i64 {
+        self.last - self.first + 1
+    }
+    pub fn range(self) -> std::ops::RangeInclusive<i64> {
+        self.first..=self.last
+    }
+}
+
+pub const fn numeral(generation: u8) -> &'static str {
+    match generation {
+        1 => "I",
+        2 => "II",
+        3 => "III",
+        4 => "IV",
+        5 => "V",
+        6 => "VI",
+        7 => "VII",
+        8 => "VIII",
+        9 => "IX",
+        _ => "?",
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn percent(count: usize, total: i64) -> f64 {
+    if total <= 0 {
+        return 0.0;
+    }
+
+    count as f64 * 100.0 / total as f64
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    pub seen: BTreeSet<i64>,
+    pub caught: BTreeSet<i64>,
+    pub milestones: BTreeSet<u8>,
+}
+
+impl Progress {
+    pub const DIR: &str = "progress";
+    pub const MILESTONES: [u8; 4] = [10, 25, 50, 100];
+
+    pub fn see(&mut self, species_id: i64) {
+        self.seen.insert(species_id);
+    }
+    pub fn catch(&mut self, species_id: i64) -> Option<u8> {
+        self.seen.insert(species_id);
+        self.caught.insert(species_id);
+
+        let mut reached = None;
+
+        for milestone in Self::MILESTONES {
+            if self.has_reached(milestone) && self.milestones.insert(milestone) {
+                reached = Some(milestone);
+            }
+        }
+
+        reached
+    }
+    pub fn seed(&mut self, species: impl IntoIterator<Item = i64>) {
+        for species_id in species {
+            self.seen.insert(species_id);
+            self.caught.insert(species_id);
+        }
+
+        for milestone in Self::MILESTONES {
+            if self.has_reached(milestone) {
+                self.milestones.insert(milestone);
+            }
+        }
+    }
+
+    pub fn has_reached(&self, milestone: u8) -> bool {
+        let caught = i64::try_from(self.caught.len()).unwrap_or(i64::MAX);
+
+        caught.saturating_mul(100) >= i64::from(milestone) * Encounter::DEX_SIZE
+    }
+    pub fn seen_in(&self, region: Region) -> usize {
+        self.seen.range(region.range()).count()
+    }
+    pub fn caught_in(&self, region: Region) -> usize {
+        self.caught.range(region.range()).count()
+    }
+    pub fn missing(&self) -> Vec<i64> {
+        (1..=Encounter::DEX_SIZE)
+            .filter(|id| !self.caught.contains(id))
+            .collect()
+    }
+}
+
+impl Versioned for Progress {}
@@ -0,0 +1,327 @@
+use std::sync::{Mutex, MutexGuard};
+
+use rustemon::model::pokemon::PokemonSpecies;
+
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Baby,
+    Legendary,
+    Mythical,
+}
+
+impl Rarity {
+    pub const ALL: [Self; 6] = [
+        Self::Common,
+        Self::Uncommon,
+        Self::Rare,
+        Self::Baby,
+        Self::Legendary,
+        Self::Mythical,
+    ];
+
+    pub const fn of(species: &PokemonSpecies) -> Self {
+        if species.is_mythical {
+            Self::Mythical
+        } else if species.is_legendary {
+            Self::Legendary
+        } else if species.is_baby {
+            Self::Baby
+        } else {
+            Self::from_capture_rate(species.capture_rate)
+        }
+    }
+    pub const fn from_capture_rate(capture_rate: i64) -> Self {
+        match capture_rate {
+            i64::MIN..=45 => Self::Rare,
+            46..=120 => Self::Uncommon,
+            _ => Self::Common,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Common => "Common",
+            Self::Uncommon => "Uncommon",
+            Self::Rare => "Rare",
+            Self::Baby => "Baby",
+            Self::Legendary => "Legendary",
+            Self::Mythical => "Mythical",
+        }
+    }
+}
+
+#[async_trait]
+pub trait RarityLookup {
+    async fn rarity(&self, species_id: i64) -> Result<Rarity>;
+}
+
+#[async_trait]
+impl RarityLookup for PokeApi {
+    async fn rarity(&self, species_id: i64) -> Result<Rarity> {
+        Ok(Rarity::of(&self.species(species_id).await?))
+    }
+}
+
+#[async_trait]
+impl<L> RarityLookup for Arc<L>
+where
+    L: RarityLookup + Send + Sync + ?Sized,
+{
+    async fn rarity(&self, species_id: i64) -> Result<Rarity> {
+        (**self).rarity(species_id).await
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RarityCache<L> {
+    lookup: L,
+    rarities: Mutex<BTreeMap<i64, Rarity>>,
+}
+
+impl<L> RarityCache<L> {
+    pub fn new(lookup: L) -> Self {
+        Self {
+            lookup,
+            rarities: Mutex::default(),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, BTreeMap<i64, Rarity>>> {
+        self.rarities
+            .lock()
+            .map_err(|_| anyhow!("poisoned rarity lock"))
+    }
+}
+
+#[async_trait]
+impl<L> RarityLookup for RarityCache<L>
+where
+    L: RarityLookup + Send + Sync,
+{
+    async fn rarity(&self, species_id: i64) -> Result<Rarity> {
+        let cached = self.lock()?.get(&species_id).copied();
+
+        if let Some(rarity) = cached {
+            return Ok(rarity);
+        }
+
+        let rarity = self.lookup.rarity(species_id).await?;
+        self.lock()?.insert(species_id, rarity);
+
+        Ok(rarity)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RollTable {
+    pub weights: BTreeMap<Rarity, u32>,
+    pub shiny_chance: f64,
+    pub max_attempts: usize,
+}
+
+impl RollTable {
+    pub const DIR: &str = "config";
+    pub const KEY: &str = "rarity";
+
+    pub async fn load() -> Result<Self> {
+        if Data::<Self>::exists(Self::DIR, Self::KEY).await? {
+            Ok(Data::load(Self::DIR, Self::KEY).await?.res_unwrap())
+        } else {
+            let table = Self::default();
+            Data::new(Self::DIR, Self::KEY, table.clone())
+                .res_save()
+                .await?;
+
+            Ok(table)
+        }
+    }
+
+    pub fn weight(&self, rarity: Rarity) -> u32 {
+        self.weights.get(&rarity).copied().unwrap_or_default()
+    }
+    pub fn max_weight(&self) -> u32 {
+        self.weights.values().copied().max().unwrap_or_default()
+    }
+    pub fn accepts(&self, rng: &mut impl Rng, rarity: Rarity) -> bool {
+        let max = self.max_weight();
+
+        max > 0 && rng.gen_ratio(self.weight(rarity).min(max), max)
+    }
+
+    pub async fn roll_species<R>(
+        &self,
+        lookup: &(impl RarityLookup + Sync),
+        rng: &mut R,
+    ) -> Result<i64>
+    where
+        R: Rng + Send,
+    {
+        let attempts = self.max_attempts.max(1);
+        let mut fallback: Option<(u32, i64)> = None;
+
+        for _ in 0..attempts {
+            let species_id = rng.gen_range(1..=Encounter::DEX_SIZE);
+            let rarity = lookup.rarity(species_id).await?;
+
+            if self.accepts(rng, rarity) {
+                return Ok(species_id);
+            }
+
+            let weight = self.weight(rarity);
+
+            if weight > 0 && fallback.is_none_or(|(best, _)| weight > best) {
+                fallback = Some((weight, species_id));
+            }
+        }
+
+        fallback
+            .map(|(_, species_id)| species_id)
+            .ok_or_else(|| anyhow!("no species was accepted after {attempts} attempts"))
+    }
+    pub async fn roll<R>(
+        &self,
+        lookup: &(impl RarityLookup + Sync),
+        rng: &mut R,
+    ) -> Result<Encounter>
+    where
+        R: Rng + Send,
+    {
+        let species_id = self.roll_species(lookup, rng).await?;
+
+        Ok(Encounter::roll_for(rng, species_id, self.shiny_chance))
+    }
+}
+
+impl Versioned for RollTable {}
+
+impl Default for RollTable {
+    fn default() -> Self {
+        let weights = Rarity::ALL
+            .into_iter()
+            .zip([100, 50, 20, 30, 2, 1])
+            .collect();
+
+        Self {
+            weights,
+            shiny_chance: 1.0 / 4096.0,
+            max_attempts: 64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct Stub {
+        lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RarityLookup for Stub {
+        async fn rarity(&self, species_id: i64) -> Result<Rarity> {
+            self.lookups.fetch_add(1, Ordering::Relaxed);
+
+            Ok(if species_id % 2 == 0 {
+                Rarity::Common
+            } else {
+                Rarity::Rare
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Unweighted;
+
+    #[async_trait]
+    impl RarityLookup for Unweighted {
+        async fn rarity(&self, species_id: i64) -> Result<Rarity> {
+            Ok(if species_id % 2 == 0 {
+                Rarity::Uncommon
+            } else {
+                Rarity::Mythical
+            })
+        }
+    }
+
+    fn table(weights: [(Rarity, u32); 2], max_attempts: usize) -> RollTable {
+        RollTable {
+            weights: weights.into_iter().collect(),
+            shiny_chance: 0.5,
+            max_attempts,
+        }
+    }
+
+    #[tokio::test]
+    async fn rolls_are_repeatable() -> Result<()> {
+        let table = RollTable::default();
+        let stub = Stub::default();
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let encounter = table.roll(&stub, &mut first).await?;
+
+            assert_eq!(encounter, table.roll(&stub, &mut second).await?);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rolls_follow_weights() -> Result<()> {
+        let table = table([(Rarity::Common, 100), (Rarity::Rare, 25)], 64);
+        let stub = Stub::default();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut rare = 0;
+
+        for _ in 0..4000 {
+            if table.roll_species(&stub, &mut rng).await? % 2 != 0 {
+                rare += 1;
+            }
+        }
+
+        assert!(
+            (600..=1000).contains(&rare),
+            "{rare} rare rolls out of 4000"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_weighted_species() -> Result<()> {
+        let table = table([(Rarity::Common, 100), (Rarity::Uncommon, 10)], 2);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..500 {
+            match table.roll_species(&Unweighted, &mut rng).await {
+                Ok(species_id) => assert_eq!(species_id % 2, 0, "rolled #{species_id}"),
+                Err(error) => assert!(error.to_string().contains("no species")),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn caches_rarity_lookups() -> Result<()> {
+        let cache = RarityCache::new(Stub::default());
+
+        for species_id in [1, 2, 1, 2, 3, 1] {
+            cache.rarity(species_id).await?;
+        }
+
+        assert_eq!(cache.lookup.lookups.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    }
+}
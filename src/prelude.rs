@@ -2,17 +2,18 @@ pub use std::{
     collections::{BTreeMap, BTreeSet, VecDeque},
     fmt::Display,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 pub use anyhow::{anyhow, Result};
 pub use chrono::prelude::*;
 pub use rand::prelude::*;
-pub use rustemon::{client::*, Follow};
+pub use rustemon::client::*;
 pub use serde::{Deserialize, Serialize};
 pub use serenity::{
     all::{
-        async_trait, ActionRow, ActionRowComponent, CacheHttp, Channel, Client, Color, Command,
-        CommandInteraction, CommandOptionType, CommandPermission, CommandPermissionData,
+        async_trait, ActionRow, ActionRowComponent, ButtonStyle, CacheHttp, Channel, Client, Color,
+        Command, CommandInteraction, CommandOptionType, CommandPermission, CommandPermissionData,
         CommandPermissionType, ComponentInteraction, Context, EventHandler, GatewayIntents,
         GuildChannel, Http, Interaction, Message, ModalInteraction, PartialChannel, PartialGuild,
         PartialMember, Permissions, PrivateChannel, ResolvedOption, ResolvedValue, Role, User,
@@ -24,5 +25,9 @@ pub use serenity::{
 pub use crate::{
     command::*,
     event::*,
-    util::{anchor::*, custom_id::*, data::*, logger::*, timestamp::*, traits::*},
+    model::{collection::*, encounter::*, guild::*, names::*, progress::*, roll::*},
+    util::{
+        anchor::*, cooldown::*, custom_id::*, data::*, format::*, logger::*, pokeapi::*,
+        presence::*, storage::*, store::*, timestamp::*, traits::*, version::*,
+    },
 };
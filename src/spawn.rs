@@ -0,0 +1,351 @@
+use std::{
+    num::NonZeroU64,
+    sync::{Mutex, MutexGuard},
+};
+
+use serenity::all::Mentionable;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::{error, handler, prelude::*};
+
+pub const NAME: &str = "spawn";
+pub const LIFETIME: i64 = 300;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spawn {
+    pub encounter: Encounter,
+    pub name: String,
+    pub types: Vec<String>,
+    pub anchor: Anchor,
+    pub expires: DateTime<Utc>,
+}
+
+impl Spawn {
+    pub fn new(info: &EncounterInfo, anchor: Anchor, expires: DateTime<Utc>) -> Self {
+        Self {
+            encounter: info.encounter,
+            name: info.name(),
+            types: info.types(),
+            anchor,
+            expires,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Utc::now()
+    }
+    pub fn to_caught(&self) -> Caught {
+        let Encounter {
+            species_id,
+            level,
+            shiny,
+            ivs,
+        } = self.encounter;
+
+        Caught {
+            species_id,
+            name: self.name.clone(),
+            types: self.types.clone(),
+            nickname: None,
+            level,
+            shiny,
+            ivs,
+            caught_at: Utc::now(),
+            anchor: Some(self.anchor),
+        }
+    }
+}
+
+impl Versioned for Vec<Spawn> {}
+
+impl ToButtons for Spawn {
+    type Args = ();
+
+    fn to_buttons(&self, disabled: bool, _: Self::Args) -> Vec<CreateButton> {
+        buttons(disabled)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Spawns {
+    spawns: Arc<Mutex<BTreeMap<MessageId, Spawn>>>,
+    saving: Arc<AsyncMutex<()>>,
+}
+
+impl Spawns {
+    pub const DIR: &str = "state";
+    pub const KEY: &str = "spawns";
+
+    pub async fn load() -> Result<Vec<Spawn>> {
+        if Data::<Vec<Spawn>>::exists(Self::DIR, Self::KEY).await? {
+            Ok(Data::load(Self::DIR, Self::KEY).await?.res_unwrap())
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<BTreeMap<MessageId, Spawn>>> {
+        self.spawns
+            .lock()
+            .map_err(|_| anyhow!("poisoned spawn lock"))
+    }
+
+    pub fn insert(&self, spawn: Spawn) -> Result<()> {
+        self.lock()?.insert(spawn.anchor.message_id, spawn);
+
+        Ok(())
+    }
+    pub fn claim(&self, message_id: MessageId) -> Result<Option<Spawn>> {
+        let mut spawns = self.lock()?;
+
+        if spawns.get(&message_id).is_none_or(Spawn::is_expired) {
+            return Ok(None);
+        }
+
+        Ok(spawns.remove(&message_id))
+    }
+    pub fn take_expired(&self) -> Result<Vec<Spawn>> {
+        let mut spawns = self.lock()?;
+        let expired: Vec<_> = spawns
+            .iter()
+            .filter(|(_, spawn)| spawn.is_expired())
+            .map(|(id, _)| *id)
+            .collect();
+
+        Ok(expired
+            .into_iter()
+            .filter_map(|id| spawns.remove(&id))
+            .collect())
+    }
+    pub fn all(&self) -> Result<Vec<Spawn>> {
+        Ok(self.lock()?.values().cloned().collect())
+    }
+
+    pub async fn save(&self) -> Result<()> {
+        let _guard = self.saving.lock().await;
+        let spawns = self.all()?;
+
+        Data::new(Self::DIR, Self::KEY, spawns).res_save().await
+    }
+}
+
+pub fn buttons(disabled: bool) -> Vec<CreateButton> {
+    let custom_id = CustomId::new(NAME, "catch");
+
+    vec![CreateButton::new(custom_id.to_string())
+        .disabled(disabled)
+        .emoji('🔴')
+        .label("Catch")
+        .style(ButtonStyle::Success)]
+}
+
+pub async fn tick(events: &Events, http: &Http, rng: &mut StdRng) -> Result<usize> {
+    let logger = &events.logger;
+    let expired = events.spawns.take_expired()?;
+
+    for spawn in &expired {
+        if let Err(error) = flee(http, spawn).await {
+            let time = Local::now();
+
+            error!(logger, time, "Spawn could not flee: {error}");
+        }
+    }
+
+    if !expired.is_empty() {
+        events.spawns.save().await?;
+    }
+
+    let mut spawned = 0;
+    let now = Utc::now();
+
+    for key in events.guilds.keys().await? {
+        let guild_id = match key.parse::<NonZeroU64>() {
+            Ok(id) => GuildId::from(id),
+            Err(error) => {
+                let time = Local::now();
+
+                error!(logger, time, "Invalid settings key \"{key}\": {error}");
+                continue;
+            }
+        };
+        let settings = match events.guilds.lock(&key).await {
+            Ok(settings) => settings.clone(),
+            Err(error) => {
+                let time = Local::now();
+
+                error!(logger, time, "Settings failed to load: {error}");
+                continue;
+            }
+        };
+
+        if settings.is_quiet(now) || !rng.gen_bool(settings.chance()) {
+            continue;
+        }
+
+        match spawn_in(events, http, rng, guild_id, &settings).await {
+            Ok(Some(_)) => spawned += 1,
+            Ok(None) => {}
+            Err(error) => {
+                let time = Local::now();
+
+                error!(logger, time, "Spawn failed in {guild_id}: {error}");
+            }
+        }
+    }
+
+    Ok(spawned)
+}
+
+pub async fn spawn_in(
+    events: &Events,
+    http: &Http,
+    rng: &mut StdRng,
+    guild_id: GuildId,
+    settings: &GuildSettings,
+) -> Result<Option<Message>> {
+    let channels: Vec<_> = settings.channels.iter().copied().collect();
+
+    let Some(channel_id) = channels.choose(rng).copied() else {
+        return Ok(None);
+    };
+
+    let encounter = events.table.roll(&*events.rarities, rng).await?;
+
+    spawn(events, http, guild_id, channel_id, encounter)
+        .await
+        .map(Some)
+}
+
+pub async fn spawn(
+    events: &Events,
+    http: &Http,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    encounter: Encounter,
+) -> Result<Message> {
+    let info = EncounterInfo::fetch(&events.pokeapi, encounter).await?;
+    let expires = Utc::now() + chrono::Duration::seconds(LIFETIME);
+    let embed = info
+        .to_embed(())
+        .description(format!(
+            "Quick, catch it before it flees {}!",
+            Timestamp::from(expires)
+        ))
+        .title(format!("A wild {} appeared!", info.name()));
+
+    let builder = CreateMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(buttons(false))]);
+    let message = channel_id.send_message(http, builder).await?;
+
+    let anchor = Anchor::new_guild(guild_id, channel_id, message.id);
+    events.spawns.insert(Spawn::new(&info, anchor, expires))?;
+    events.spawns.save().await?;
+
+    progress::see(events, channel_id, encounter.species_id).await?;
+
+    Ok(message)
+}
+
+pub async fn flee(http: &Http, spawn: &Spawn) -> Result<()> {
+    let mut message = spawn.anchor.to_message(http).await?;
+    let embed = message
+        .embeds
+        .first()
+        .cloned()
+        .map(CreateEmbed::from)
+        .unwrap_or_default()
+        .description("Better luck next time!")
+        .title(format!("The wild {} fled!", spawn.name));
+
+    let builder = EditMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(spawn.to_buttons(true, ()))]);
+
+    message.edit(http, builder).await.map_err(Into::into)
+}
+
+pub async fn set_disabled(http: &Http, spawn: &Spawn, disabled: bool) -> Result<()> {
+    let Anchor {
+        channel_id,
+        message_id,
+        ..
+    } = spawn.anchor;
+    let builder = EditMessage::new().components(vec![CreateActionRow::Buttons(
+        spawn.to_buttons(disabled, ()),
+    )]);
+
+    channel_id
+        .edit_message(http, message_id, builder)
+        .await
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+pub async fn component(
+    events: &Events,
+    context: &Context,
+    component: &ComponentInteraction,
+    custom_id: &CustomId<'_>,
+) -> Result<()> {
+    match custom_id.name {
+        "catch" => catch(events, context, component).await,
+        _ => Err(anyhow!("invalid custom identifier name")),
+    }
+}
+
+async fn catch(events: &Events, context: &Context, component: &ComponentInteraction) -> Result<()> {
+    let Some(spawn) = events.spawns.claim(component.message.id)? else {
+        let embed = CreateEmbed::new()
+            .color(Color::RED)
+            .description("This Pokémon has already been caught or has fled.")
+            .title("Too slow!");
+        let message = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true);
+
+        let builder = CreateInteractionResponse::Message(message);
+        component.create_response(context, builder).await?;
+
+        return Ok(());
+    };
+
+    events.spawns.save().await?;
+
+    let user = &component.user;
+    let key = user.id.to_string();
+
+    events.collections.lock(&key).await?.push(spawn.to_caught());
+
+    let embed = component
+        .message
+        .embeds
+        .first()
+        .cloned()
+        .map(CreateEmbed::from)
+        .unwrap_or_default()
+        .description(format!("Caught by {}!", user.mention()))
+        .title(format!("The wild {} was caught!", spawn.name));
+    let message = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(spawn.to_buttons(true, ()))]);
+
+    let builder = CreateInteractionResponse::UpdateMessage(message);
+    component.create_response(context, builder).await?;
+
+    let species_id = spawn.encounter.species_id;
+    progress::catch(
+        events,
+        context.http(),
+        component.channel_id,
+        user,
+        species_id,
+    )
+    .await?;
+
+    Ok(())
+}
+
+handler! {
+    component: component,
+}
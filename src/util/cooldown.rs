@@ -0,0 +1,94 @@
+use std::sync::{Mutex, MutexGuard};
+
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub name: String,
+    pub user_id: UserId,
+    pub until: DateTime<Utc>,
+}
+
+impl Versioned for Vec<Cooldown> {}
+
+#[derive(Clone, Debug, Default)]
+pub struct Cooldowns {
+    cooldowns: Arc<Mutex<BTreeMap<(String, UserId), DateTime<Utc>>>>,
+    saving: Arc<AsyncMutex<()>>,
+}
+
+impl Cooldowns {
+    pub const DIR: &str = "state";
+    pub const KEY: &str = "cooldowns";
+
+    fn lock(&self) -> Result<MutexGuard<BTreeMap<(String, UserId), DateTime<Utc>>>> {
+        self.cooldowns
+            .lock()
+            .map_err(|_| anyhow!("poisoned cooldown lock"))
+    }
+
+    pub async fn save(&self) -> Result<()> {
+        let _guard = self.saving.lock().await;
+        let cooldowns: Vec<_> = self
+            .lock()?
+            .iter()
+            .map(|((name, user_id), until)| Cooldown {
+                name: name.clone(),
+                user_id: *user_id,
+                until: *until,
+            })
+            .collect();
+
+        Data::new(Self::DIR, Self::KEY, cooldowns).res_save().await
+    }
+    pub async fn restore(&self) -> Result<usize> {
+        if !Data::<Vec<Cooldown>>::exists(Self::DIR, Self::KEY).await? {
+            return Ok(0);
+        }
+
+        let cooldowns = Data::<Vec<Cooldown>>::load(Self::DIR, Self::KEY).await?;
+        let now = Utc::now();
+        let active: Vec<_> = cooldowns
+            .res_unwrap()
+            .into_iter()
+            .filter(|cooldown| cooldown.until > now)
+            .collect();
+        let count = active.len();
+
+        self.lock()?.extend(active.into_iter().map(
+            |Cooldown {
+                 name,
+                 user_id,
+                 until,
+             }| ((name, user_id), until),
+        ));
+
+        Ok(count)
+    }
+
+    pub fn try_start(
+        &self,
+        name: &str,
+        user_id: UserId,
+        duration: chrono::Duration,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let mut map = self.lock()?;
+        let now = Utc::now();
+
+        map.retain(|_, until| *until > now);
+
+        if let Some(until) = map.get(&(name.to_string(), user_id)) {
+            return Ok(Some(*until));
+        }
+
+        map.insert((name.to_string(), user_id), now + duration);
+        Ok(None)
+    }
+    pub fn clear(&self, name: &str, user_id: UserId) -> Result<()> {
+        self.lock()?.remove(&(name.to_string(), user_id));
+
+        Ok(())
+    }
+}
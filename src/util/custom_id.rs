@@ -1,28 +1,31 @@
+use std::{borrow::Cow, error::Error, str::FromStr};
+
 use crate::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CustomId<'c> {
     pub base: &'c str,
     pub name: &'c str,
-    pub data: Vec<&'c str>,
+    pub data: Vec<Cow<'c, str>>,
 }
 
 impl<'c> CustomId<'c> {
+    pub const ESCAPE: char = '\\';
+    pub const HEADER: char = '_';
+    pub const SEPARATOR: char = ';';
+
     pub const fn new(base: &'c str, name: &'c str) -> Self {
         let data = vec![];
 
         Self { base, name, data }
     }
-    pub fn try_resolve(head: &'c str) -> Result<Self> {
-        let Some([base, name]) = head.split('_').array_chunks().next() else {
-            return Err(anyhow!("invalid custom identifier header"));
-        };
-
-        Ok(Self::new(base, name))
+    pub fn try_resolve(custom_id: &'c str) -> Result<Self> {
+        Self::try_from(custom_id)
     }
 
-    pub fn push_data(&mut self, arg: &'c str) -> Result<()> {
-        let length = self.to_string().len() + arg.len() + 1;
+    pub fn push_data(&mut self, arg: impl Into<Cow<'c, str>>) -> Result<()> {
+        let arg = arg.into();
+        let length = self.to_string().len() + escape(&arg).len() + 1;
 
         if length > 64 {
             return Err(anyhow!("invalid custom identifier length ({length} > 64)"));
@@ -31,6 +34,41 @@ impl<'c> CustomId<'c> {
         self.data.push(arg);
         Ok(())
     }
+    pub fn data(&self, index: usize) -> Result<&str> {
+        self.data
+            .get(index)
+            .map(Deref::deref)
+            .ok_or_else(|| anyhow!("missing custom identifier data at {index}"))
+    }
+    pub fn data_as<T>(&self, index: usize) -> Result<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Error + Send + Sync + 'static,
+    {
+        self.data(index)?.parse().map_err(Into::into)
+    }
+}
+
+impl<'c> TryFrom<&'c str> for CustomId<'c> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &'c str) -> Result<Self> {
+        let mut segments = split(value, Self::SEPARATOR).into_iter();
+        let head = segments.next().unwrap_or_default();
+
+        let Some((base, name)) = head.split_once(Self::HEADER) else {
+            return Err(anyhow!("invalid custom identifier header"));
+        };
+
+        if base.is_empty() || name.is_empty() || name.contains(Self::HEADER) {
+            return Err(anyhow!("invalid custom identifier header"));
+        }
+
+        let mut custom_id = Self::new(base, name);
+        custom_id.data = segments.map(unescape).collect::<Result<_>>()?;
+
+        Ok(custom_id)
+    }
 }
 
 impl From<&CustomId<'_>> for String {
@@ -43,12 +81,141 @@ impl Display for CustomId<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let CustomId { base, name, data } = self;
 
-        if data.is_empty() {
-            write!(f, "{base}_{name}")
-        } else {
-            let data = data.join(";");
+        write!(f, "{base}{}{name}", Self::HEADER)?;
+
+        for arg in data {
+            write!(f, "{}{}", Self::SEPARATOR, escape(arg))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape(value: &str) -> Cow<'_, str> {
+    let special = [CustomId::ESCAPE, CustomId::HEADER, CustomId::SEPARATOR];
+
+    if !value.contains(special) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 1);
+
+    for c in value.chars() {
+        if special.contains(&c) {
+            escaped.push(CustomId::ESCAPE);
+        }
+
+        escaped.push(c);
+    }
+
+    Cow::Owned(escaped)
+}
+
+fn unescape(value: &str) -> Result<Cow<'_, str>> {
+    if !value.contains(CustomId::ESCAPE) {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != CustomId::ESCAPE {
+            unescaped.push(c);
+            continue;
+        }
+
+        let Some(next) = chars.next() else {
+            return Err(anyhow!("invalid custom identifier escape"));
+        };
+
+        unescaped.push(next);
+    }
+
+    Ok(Cow::Owned(unescaped))
+}
+
+fn split(value: &str, delimiter: char) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == CustomId::ESCAPE {
+            escaped = true;
+        } else if c == delimiter {
+            segments.push(&value[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+
+    segments.push(&value[start..]);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[&str]) -> Result<()> {
+        let mut custom_id = CustomId::new("base", "name");
+
+        for arg in data {
+            custom_id.push_data(*arg)?;
+        }
+
+        let string = custom_id.to_string();
+        let parsed = CustomId::try_resolve(&string)?;
+
+        assert_eq!(parsed, custom_id, "{string}");
+        assert_eq!(parsed.data, data, "{string}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_without_data() -> Result<()> {
+        round_trip(&[])?;
+
+        assert_eq!(CustomId::new("base", "name").to_string(), "base_name");
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_special_characters() -> Result<()> {
+        round_trip(&["snake_case", "a;b", "back\\slash", "\\_;"])?;
+        round_trip(&["_", ";", "\\"])
+    }
+
+    #[test]
+    fn round_trips_empty_segments() -> Result<()> {
+        round_trip(&[""])?;
+        round_trip(&["", "", "x", ""])
+    }
+
+    #[test]
+    fn escapes_separators() -> Result<()> {
+        let mut custom_id = CustomId::new("base", "name");
+        custom_id.push_data("a;b_c\\d")?;
+
+        assert_eq!(custom_id.to_string(), "base_name;a\\;b\\_c\\\\d");
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_dangling_escapes() {
+        assert!(CustomId::try_resolve("base_name;abc\\").is_err());
+        assert!(CustomId::try_resolve("base_name;\\").is_err());
+    }
 
-            write!(f, "{base}_{name};{data}")
+    #[test]
+    fn rejects_invalid_headers() {
+        for custom_id in ["", "base", "_name", "base_", "base_na_me;x"] {
+            assert!(CustomId::try_resolve(custom_id).is_err(), "{custom_id}");
         }
     }
 }
@@ -1,16 +1,9 @@
-use std::{
-    fs::{create_dir_all, remove_file, File},
-    io::Write,
-    path::PathBuf,
-    str::FromStr,
-};
-
 use crate::prelude::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Data<'res, T>
 where
-    T: Serialize + for<'de> Deserialize<'de>,
+    T: Versioned,
 {
     dir: &'res str,
     key: &'res str,
@@ -19,75 +12,103 @@ where
 
 impl<'res, T> Data<'res, T>
 where
-    T: Serialize + for<'de> Deserialize<'de>,
+    T: Versioned,
 {
-    pub const DIR: &str = "res";
-    pub const EXT: &str = "rmp";
-
-    pub fn root() -> PathBuf {
-        PathBuf::from(Self::DIR)
+    pub async fn dirs() -> Result<Vec<String>> {
+        storage()?.dirs().await
     }
-    pub fn dir_from(dir: &str) -> Result<PathBuf> {
-        let dir = PathBuf::from_str(dir)?;
-
-        Ok(Self::root().join(dir))
+    pub async fn keys(dir: &str) -> Result<Vec<String>> {
+        storage()?.keys(dir).await
     }
-    pub fn path_from(dir: &str, key: &str) -> Result<PathBuf> {
-        let dir = Self::dir_from(dir)?;
-        let key = PathBuf::from_str(key)?;
+    pub async fn iter(dir: &str) -> Result<Vec<Result<(String, T)>>> {
+        Self::iter_from(storage()?, dir).await
+    }
+    pub async fn iter_from(storage: &dyn Storage, dir: &str) -> Result<Vec<Result<(String, T)>>> {
+        let mut values = vec![];
+
+        for (key, value) in storage.values(dir).await? {
+            let value = match value.and_then(|(format, data)| decode(format, &data)) {
+                Ok(value) => Ok(value),
+                Err(_) => Data::load_from(storage, dir, &key)
+                    .await
+                    .map(Data::res_unwrap),
+            };
 
-        Ok(dir.join(key).with_extension(Self::EXT))
+            values.push(value.map(|value| (key, value)));
+        }
+
+        Ok(values)
+    }
+    pub async fn exists(dir: &str, key: &str) -> Result<bool> {
+        storage()?.exists(dir, key).await
     }
 
     pub const fn new(dir: &'res str, key: &'res str, value: T) -> Self {
         Self { dir, key, value }
     }
-    pub fn load(dir: &'res str, key: &'res str) -> Result<Self> {
-        let path = Self::path_from(dir, key)?;
-        let file = File::open(path)?;
-        let value = rmp_serde::from_read(file)?;
-
-        Ok(Self::new(dir, key, value))
+    pub async fn load(dir: &'res str, key: &'res str) -> Result<Self> {
+        Self::load_from(storage()?, dir, key).await
+    }
+    pub async fn load_from(storage: &dyn Storage, dir: &'res str, key: &'res str) -> Result<Self> {
+        let error = match Self::read(storage, dir, key, 0).await {
+            Ok(value) => return Ok(Self::new(dir, key, value)),
+            Err(error) => error,
+        };
+
+        for generation in 1..=storage.generations() {
+            if let Ok(value) = Self::read(storage, dir, key, generation).await {
+                return Ok(Self::new(dir, key, value));
+            }
+        }
+
+        Err(error)
     }
+    async fn read(storage: &dyn Storage, dir: &str, key: &str, generation: usize) -> Result<T> {
+        let (format, data) = storage.read(dir, key, generation).await?;
 
-    pub fn dir(&self) -> Result<PathBuf> {
-        Self::dir_from(self.dir)
+        decode(format, &data)
     }
-    pub fn path(&self) -> Result<PathBuf> {
-        Self::path_from(self.dir, self.key)
+    async fn remove(dir: &str, key: &str) -> Result<()> {
+        storage()?.remove(dir, key).await
     }
 
-    pub fn res_save(&self) -> Result<()> {
-        let data = rmp_serde::to_vec(&self.value)?;
-
-        create_dir_all(self.dir()?)?;
+    pub async fn res_save(&self) -> Result<()> {
+        self.res_save_to(storage()?).await
+    }
+    pub async fn res_save_to(&self, storage: &dyn Storage) -> Result<()> {
+        let format = storage.format(format_for(self.dir));
+        let data = encode(format, &self.value)?;
 
-        let mut file = File::create(self.path()?)?;
-        file.write_all(&data)?;
-        file.flush().map_err(Into::into)
+        storage.write(self.dir, self.key, format, data).await
     }
     #[allow(clippy::missing_const_for_fn)]
     pub fn res_unwrap(self) -> T {
         self.value
     }
-    pub fn res_delete(self) -> Result<T> {
-        remove_file(self.path()?)?;
+    pub async fn res_delete(self) -> Result<T> {
+        Self::remove(self.dir, self.key).await?;
 
         Ok(self.res_unwrap())
     }
-    pub fn res_rename(&mut self, dir: &'res str, key: &'res str) -> Result<()> {
-        remove_file(self.path()?)?;
+    pub async fn res_rename(&mut self, dir: &'res str, key: &'res str) -> Result<()> {
+        let (old_dir, old_key) = (self.dir, self.key);
+
+        if (old_dir, old_key) == (dir, key) {
+            return self.res_save().await;
+        }
 
         self.dir = dir;
         self.key = key;
-        self.res_save()
+        self.res_save().await?;
+
+        Self::remove(old_dir, old_key).await
     }
-    pub fn res_clone(&self, dir: &'res str, key: &'res str) -> Result<Self>
+    pub async fn res_clone(&self, dir: &'res str, key: &'res str) -> Result<Self>
     where
         T: Clone,
     {
         let copied = Self::new(dir, key, self.value.clone());
-        copied.res_save()?;
+        copied.res_save().await?;
 
         Ok(copied)
     }
@@ -95,7 +116,7 @@ where
 
 impl<T> Deref for Data<'_, T>
 where
-    T: Serialize + for<'de> Deserialize<'de>,
+    T: Versioned,
 {
     type Target = T;
 
@@ -106,9 +127,72 @@ where
 
 impl<T> DerefMut for Data<'_, T>
 where
-    T: Serialize + for<'de> Deserialize<'de>,
+    T: Versioned,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    struct Record {
+        value: u32,
+    }
+
+    impl Versioned for Record {}
+
+    async fn corrupt(storage: &FileStorage, key: &str) -> Result<()> {
+        let (format, _) = storage.read("dir", key, 0).await?;
+
+        tokio::fs::write(storage.path_from("dir", key, format)?, [0xc1]).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn loads_backups_of_corrupt_records() -> Result<()> {
+        let storage = temp_storage("fallback", 2)?;
+
+        for value in [1, 2] {
+            Data::new("dir", "key", Record { value })
+                .res_save_to(&storage)
+                .await?;
+        }
+
+        corrupt(&storage, "key").await?;
+
+        let data = Data::<Record>::load_from(&storage, "dir", "key").await?;
+
+        assert!(storage.exists("dir", "key").await?);
+        assert_eq!(data.res_unwrap(), Record { value: 1 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn iterates_past_corrupt_records() -> Result<()> {
+        let storage = temp_storage("iterate", 1)?;
+
+        for (key, value) in [("a", 1), ("a", 2), ("b", 3), ("c", 4)] {
+            Data::new("dir", key, Record { value })
+                .res_save_to(&storage)
+                .await?;
+        }
+
+        corrupt(&storage, "a").await?;
+        corrupt(&storage, "b").await?;
+
+        let values = Data::<Record>::iter_from(&storage, "dir").await?;
+
+        assert_eq!(values.len(), 3);
+        assert!(matches!(&values[0], Ok((key, Record { value: 1 })) if key == "a"));
+        assert!(values[1].is_err());
+        assert!(matches!(&values[2], Ok((key, Record { value: 4 })) if key == "c"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+
+use crate::prelude::*;
+
+static FORMATS: OnceLock<Formats> = OnceLock::new();
+
+pub fn set_formats(formats: Formats) -> Result<()> {
+    FORMATS
+        .set(formats)
+        .map_err(|_| anyhow!("storage formats are already set"))
+}
+pub fn format_for(dir: &str) -> Format {
+    FORMATS.get().map(|f| f.get(dir)).unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Format {
+    #[default]
+    #[value(name = "rmp")]
+    MessagePack,
+    #[value(name = "json")]
+    Json,
+    #[value(name = "ron")]
+    Ron,
+}
+
+impl Format {
+    pub const ALL: [Self; 3] = [Self::MessagePack, Self::Json, Self::Ron];
+
+    pub const fn ext(self) -> &'static str {
+        match self {
+            Self::MessagePack => "rmp",
+            Self::Json => "json",
+            Self::Ron => "ron",
+        }
+    }
+    pub fn from_ext(ext: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.ext() == ext)
+    }
+
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>> {
+        Ok(match self {
+            Self::MessagePack => rmp_serde::to_vec_named(value)?,
+            Self::Json => serde_json::to_vec_pretty(value)?,
+            Self::Ron => ron::ser::to_string_pretty(value, PrettyConfig::default())?.into_bytes(),
+        })
+    }
+    pub fn decode<T: DeserializeOwned>(self, data: &[u8]) -> Result<T> {
+        Ok(match self {
+            Self::MessagePack => rmp_serde::from_slice(data)?,
+            Self::Json => serde_json::from_slice(data)?,
+            Self::Ron => ron::de::from_bytes(data)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Formats {
+    default: Format,
+    dirs: BTreeMap<String, Format>,
+}
+
+impl Formats {
+    pub fn new(default: Format, dirs: impl IntoIterator<Item = (String, Format)>) -> Self {
+        let dirs = dirs.into_iter().collect();
+
+        Self { default, dirs }
+    }
+
+    pub fn get(&self, dir: &str) -> Format {
+        self.dirs.get(dir).copied().unwrap_or(self.default)
+    }
+}
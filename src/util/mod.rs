@@ -1,6 +1,13 @@
 pub mod anchor;
+pub mod cooldown;
 pub mod custom_id;
 pub mod data;
+pub mod format;
 pub mod logger;
+pub mod pokeapi;
+pub mod presence;
+pub mod storage;
+pub mod store;
 pub mod timestamp;
 pub mod traits;
+pub mod version;
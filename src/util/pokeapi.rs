@@ -0,0 +1,185 @@
+use rustemon::model::{
+    evolution::EvolutionChain,
+    moves::Move,
+    pokemon::{Pokemon, PokemonSpecies, Type},
+};
+
+use crate::{info, prelude::*};
+
+impl Versioned for PokemonSpecies {}
+impl Versioned for Pokemon {}
+impl Versioned for EvolutionChain {}
+impl Versioned for Type {}
+impl Versioned for Move {}
+
+pub enum PokeApi {
+    Online(RustemonClient),
+    Offline(Box<dyn Storage>),
+}
+
+impl PokeApi {
+    pub const DIR: &str = "pokeapi";
+
+    pub fn online() -> Self {
+        Self::Online(RustemonClient::new(
+            CacheMode::Default,
+            Some(CacheOptions::default()),
+        ))
+    }
+    pub fn offline(storage: Box<dyn Storage>) -> Self {
+        Self::Offline(storage)
+    }
+
+    async fn read<T: Versioned>(storage: &dyn Storage, key: &str) -> Result<T> {
+        let (format, data) = storage.read(Self::DIR, key, 0).await?;
+
+        decode(format, &data)
+    }
+    async fn load<T: Versioned>(storage: &dyn Storage, kind: &str, id: i64) -> Result<T> {
+        let key = format!("{kind}-{id}");
+
+        if !storage.exists(Self::DIR, &key).await? {
+            return Err(anyhow!("{kind} #{id} is missing from the snapshot"));
+        }
+
+        Self::read(storage, &key).await
+    }
+    async fn save<T: Versioned>(kind: &str, id: i64, value: T) -> Result<()> {
+        let key = format!("{kind}-{id}");
+
+        Data::new(Self::DIR, &key, value).res_save().await
+    }
+
+    pub async fn names(&self) -> Result<NameIndex> {
+        match self {
+            Self::Online(client) => NameIndex::fetch(client).await,
+            Self::Offline(storage) => Self::read(storage.as_ref(), "names").await,
+        }
+    }
+    pub async fn species(&self, id: i64) -> Result<PokemonSpecies> {
+        match self {
+            Self::Online(client) => {
+                Ok(rustemon::pokemon::pokemon_species::get_by_id(id, client).await?)
+            }
+            Self::Offline(storage) => Self::load(storage.as_ref(), "species", id).await,
+        }
+    }
+    pub async fn pokemon(&self, id: i64) -> Result<Pokemon> {
+        match self {
+            Self::Online(client) => Ok(rustemon::pokemon::pokemon::get_by_id(id, client).await?),
+            Self::Offline(storage) => Self::load(storage.as_ref(), "pokemon", id).await,
+        }
+    }
+    pub async fn evolution_chain(&self, id: i64) -> Result<EvolutionChain> {
+        match self {
+            Self::Online(client) => {
+                Ok(rustemon::evolution::evolution_chain::get_by_id(id, client).await?)
+            }
+            Self::Offline(storage) => Self::load(storage.as_ref(), "chain", id).await,
+        }
+    }
+    pub async fn type_(&self, id: i64) -> Result<Type> {
+        match self {
+            Self::Online(client) => Ok(rustemon::pokemon::type_::get_by_id(id, client).await?),
+            Self::Offline(storage) => Self::load(storage.as_ref(), "type", id).await,
+        }
+    }
+    pub async fn move_(&self, id: i64) -> Result<Move> {
+        match self {
+            Self::Online(client) => Ok(rustemon::moves::move_::get_by_id(id, client).await?),
+            Self::Offline(storage) => Self::load(storage.as_ref(), "move", id).await,
+        }
+    }
+
+    pub async fn snapshot(&self, logger: &Logger) -> Result<usize> {
+        let Self::Online(client) = self else {
+            return Err(anyhow!("a snapshot cannot be built while offline"));
+        };
+
+        let names = NameIndex::fetch(client).await?;
+        let mut chains = BTreeSet::new();
+        let mut count = 0;
+
+        for entry in names.entries(NameKind::Species) {
+            let species = self.species(entry.id).await?;
+
+            for variety in &species.varieties {
+                if let Some(id) = resource_id(&variety.pokemon.url) {
+                    Self::save("pokemon", id, self.pokemon(id).await?).await?;
+                    count += 1;
+                }
+            }
+
+            chains.extend(resource_id(&species.evolution_chain.url));
+            Self::save("species", entry.id, species).await?;
+            count += 1;
+        }
+
+        info!(logger, "Saved {count} species and Pokémon");
+
+        for id in chains {
+            Self::save("chain", id, self.evolution_chain(id).await?).await?;
+            count += 1;
+        }
+
+        let types =
+            rustemon::pokemon::type_::get_page_with_param(0, NameIndex::LIMIT, client).await?;
+
+        for id in types.results.iter().filter_map(|t| resource_id(&t.url)) {
+            Self::save("type", id, self.type_(id).await?).await?;
+            count += 1;
+        }
+
+        for entry in names.entries(NameKind::Move) {
+            Self::save("move", entry.id, self.move_(entry.id).await?).await?;
+            count += 1;
+        }
+
+        Data::new(Self::DIR, "names", names).res_save().await?;
+
+        Ok(count + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_the_offline_snapshot() -> Result<()> {
+        let pokeapi = PokeApi::offline(Box::new(FileStorage::with_root("tests/fixtures", 0)));
+
+        let names = pokeapi.names().await?;
+        let entry = names.get(NameKind::Species, 25);
+
+        assert_eq!(entry.map(|e| e.label.as_str()), Some("Pikachu"));
+        assert_eq!(names.entries(NameKind::Species).len(), 2);
+
+        let species = pokeapi.species(25).await?;
+        let varieties: Vec<_> = species
+            .varieties
+            .iter()
+            .filter_map(|v| resource_id(&v.pokemon.url))
+            .collect();
+
+        assert_eq!(species.name, "pikachu");
+        assert_eq!(species.capture_rate, 190);
+        assert_eq!(varieties, [25]);
+        assert_eq!(resource_id(&species.evolution_chain.url), Some(10));
+
+        let pokemon = pokeapi.pokemon(25).await?;
+        let types: Vec<_> = pokemon
+            .types
+            .iter()
+            .map(|t| t.type_.name.as_str())
+            .collect();
+
+        assert_eq!(pokemon.name, "pikachu");
+        assert_eq!(types, ["electric"]);
+
+        assert!(pokeapi.species(26).await.is_err());
+        assert!(pokeapi.pokemon(26).await.is_err());
+
+        Ok(())
+    }
+}
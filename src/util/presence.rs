@@ -0,0 +1,56 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::prelude::*;
+
+#[derive(Clone, Debug, Default)]
+pub struct Presence(Arc<Mutex<BTreeMap<ChannelId, BTreeMap<UserId, DateTime<Utc>>>>>);
+
+impl Presence {
+    pub const WINDOW_SECS: i64 = 900;
+
+    fn lock(&self) -> Result<MutexGuard<BTreeMap<ChannelId, BTreeMap<UserId, DateTime<Utc>>>>> {
+        self.0.lock().map_err(|_| anyhow!("poisoned presence lock"))
+    }
+
+    pub fn record(&self, channel_id: ChannelId, user_id: UserId) -> Result<()> {
+        let mut channels = self.lock()?;
+
+        channels
+            .entry(channel_id)
+            .or_default()
+            .insert(user_id, Utc::now());
+
+        Ok(())
+    }
+    pub fn active(&self, channel_id: ChannelId) -> Result<Vec<UserId>> {
+        let channels = self.lock()?;
+        let since = Self::since();
+
+        Ok(channels
+            .get(&channel_id)
+            .map(|users| {
+                users
+                    .iter()
+                    .filter(|(_, at)| **at > since)
+                    .map(|(user_id, _)| *user_id)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+    pub fn prune(&self) -> Result<()> {
+        let mut channels = self.lock()?;
+        let since = Self::since();
+
+        channels.retain(|_, users| {
+            users.retain(|_, at| *at > since);
+
+            !users.is_empty()
+        });
+
+        Ok(())
+    }
+
+    fn since() -> DateTime<Utc> {
+        Utc::now() - chrono::Duration::seconds(Self::WINDOW_SECS)
+    }
+}
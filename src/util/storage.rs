@@ -0,0 +1,554 @@
+use std::{
+    ffi::OsStr,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex as SyncMutex, OnceLock},
+};
+
+use clap::ValueEnum;
+use rusqlite::{params, Connection, OptionalExtension};
+use tokio::{
+    fs::{copy, create_dir_all, metadata, read, read_dir, remove_file, rename, File},
+    io::AsyncWriteExt,
+    sync::{Mutex, OwnedMutexGuard},
+    task::spawn_blocking,
+};
+
+use crate::prelude::*;
+
+static STORAGE: OnceLock<Box<dyn Storage>> = OnceLock::new();
+
+pub fn set_storage(storage: Box<dyn Storage>) -> Result<()> {
+    STORAGE
+        .set(storage)
+        .map_err(|_| anyhow!("storage backend is already set"))
+}
+pub fn storage() -> Result<&'static dyn Storage> {
+    STORAGE
+        .get()
+        .map(Box::as_ref)
+        .ok_or_else(|| anyhow!("storage backend is not set"))
+}
+
+async fn is_file(path: &Path) -> bool {
+    metadata(path).await.is_ok_and(|m| m.is_file())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    #[default]
+    File,
+    Sqlite,
+}
+
+impl Backend {
+    pub fn open(self, backups: usize) -> Result<Box<dyn Storage>> {
+        Ok(match self {
+            Self::File => Box::new(FileStorage::new(backups)),
+            Self::Sqlite => Box::new(SqliteStorage::open()?),
+        })
+    }
+}
+
+#[async_trait]
+pub trait Storage: Debug + Send + Sync {
+    async fn dirs(&self) -> Result<Vec<String>>;
+    async fn keys(&self, dir: &str) -> Result<Vec<String>>;
+    async fn exists(&self, dir: &str, key: &str) -> Result<bool>;
+    async fn read(&self, dir: &str, key: &str, generation: usize) -> Result<(Format, Vec<u8>)>;
+    async fn write(&self, dir: &str, key: &str, format: Format, bytes: Vec<u8>) -> Result<()>;
+    async fn remove(&self, dir: &str, key: &str) -> Result<()>;
+
+    fn generations(&self) -> usize {
+        0
+    }
+    fn format(&self, format: Format) -> Format {
+        format
+    }
+
+    async fn values(&self, dir: &str) -> Result<Vec<(String, Result<(Format, Vec<u8>)>)>> {
+        let mut values = vec![];
+
+        for key in self.keys(dir).await? {
+            let value = self.read(dir, &key, 0).await;
+
+            values.push((key, value));
+        }
+
+        Ok(values)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FileStorage {
+    root: PathBuf,
+    backups: usize,
+    locks: Arc<SyncMutex<BTreeMap<String, Arc<Mutex<()>>>>>,
+}
+
+impl FileStorage {
+    pub const DIR: &str = "res";
+    pub const BAK: &str = "bak";
+    pub const TMP: &str = "tmp";
+
+    pub fn new(backups: usize) -> Self {
+        Self::with_root(Self::DIR, backups)
+    }
+    pub fn with_root(root: impl Into<PathBuf>, backups: usize) -> Self {
+        let root = root.into();
+        let locks = Arc::default();
+
+        Self {
+            root,
+            backups,
+            locks,
+        }
+    }
+
+    pub fn dir_from(&self, dir: &str) -> Result<PathBuf> {
+        let dir = PathBuf::from_str(dir)?;
+
+        Ok(self.root.join(dir))
+    }
+    pub fn path_from(&self, dir: &str, key: &str, format: Format) -> Result<PathBuf> {
+        let dir = self.dir_from(dir)?;
+        let key = PathBuf::from_str(key)?;
+
+        Ok(dir.join(key).with_extension(format.ext()))
+    }
+    pub fn backup_path_from(
+        &self,
+        dir: &str,
+        key: &str,
+        format: Format,
+        generation: usize,
+    ) -> Result<PathBuf> {
+        let path = self.path_from(dir, key, format)?;
+
+        if generation == 0 {
+            return Ok(path);
+        }
+
+        Ok(path.with_extension(format!("{}.{generation}.{}", format.ext(), Self::BAK)))
+    }
+
+    async fn lock(&self, dir: &str, key: &str) -> Result<OwnedMutexGuard<()>> {
+        let lock = {
+            let mut locks = self
+                .locks
+                .lock()
+                .map_err(|_| anyhow!("poisoned record lock"))?;
+
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry(format!("{dir}/{key}")).or_default().clone()
+        };
+
+        Ok(lock.lock_owned().await)
+    }
+    async fn rotate(&self, dir: &str, key: &str, format: Format) -> Result<()> {
+        let path = self.path_from(dir, key, format)?;
+
+        if self.backups == 0 || !is_file(&path).await {
+            return Ok(());
+        }
+
+        for generation in (1..self.backups).rev() {
+            let backup = self.backup_path_from(dir, key, format, generation)?;
+
+            if is_file(&backup).await {
+                let next = self.backup_path_from(dir, key, format, generation + 1)?;
+
+                rename(backup, next).await?;
+            }
+        }
+
+        copy(path, self.backup_path_from(dir, key, format, 1)?).await?;
+
+        Ok(())
+    }
+    async fn remove_format(&self, dir: &str, key: &str, format: Format) -> Result<()> {
+        let mut generation = 0;
+
+        loop {
+            let path = self.backup_path_from(dir, key, format, generation)?;
+
+            if is_file(&path).await {
+                remove_file(path).await?;
+            } else if generation > 0 {
+                break Ok(());
+            }
+
+            generation += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for FileStorage {
+    async fn dirs(&self) -> Result<Vec<String>> {
+        let root = &self.root;
+
+        if metadata(root).await.is_err() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = read_dir(root).await?;
+        let mut dirs = vec![];
+
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                dirs.push(name.to_string());
+            }
+        }
+
+        dirs.sort();
+        Ok(dirs)
+    }
+    async fn keys(&self, dir: &str) -> Result<Vec<String>> {
+        let dir = self.dir_from(dir)?;
+
+        if metadata(&dir).await.is_err() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = read_dir(dir).await?;
+        let mut keys = vec![];
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            let ext = path.extension().and_then(OsStr::to_str);
+
+            if !is_file(&path).await || ext.and_then(Format::from_ext).is_none() {
+                continue;
+            }
+            if let Some(key) = path.file_stem().and_then(OsStr::to_str) {
+                keys.push(key.to_string());
+            }
+        }
+
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+    async fn exists(&self, dir: &str, key: &str) -> Result<bool> {
+        for format in Format::ALL {
+            if is_file(&self.path_from(dir, key, format)?).await {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+    async fn read(&self, dir: &str, key: &str, generation: usize) -> Result<(Format, Vec<u8>)> {
+        for format in Format::ALL {
+            let path = self.backup_path_from(dir, key, format, generation)?;
+
+            if is_file(&path).await {
+                return Ok((format, read(path).await?));
+            }
+        }
+
+        Err(anyhow!("missing record \"{dir}/{key}\""))
+    }
+    async fn write(&self, dir: &str, key: &str, format: Format, bytes: Vec<u8>) -> Result<()> {
+        let path = self.path_from(dir, key, format)?;
+        let parent = self.dir_from(dir)?;
+        let temp = path.with_extension(format!("{}.{}", format.ext(), Self::TMP));
+        let _guard = self.lock(dir, key).await?;
+
+        create_dir_all(&parent).await?;
+
+        let mut file = File::create(&temp).await?;
+        file.write_all(&bytes).await?;
+        file.sync_all().await?;
+
+        self.rotate(dir, key, format).await?;
+        rename(temp, path).await?;
+
+        for other in Format::ALL.into_iter().filter(|f| *f != format) {
+            self.remove_format(dir, key, other).await?;
+        }
+
+        #[cfg(unix)]
+        File::open(parent).await?.sync_all().await?;
+
+        Ok(())
+    }
+    async fn remove(&self, dir: &str, key: &str) -> Result<()> {
+        let _guard = self.lock(dir, key).await?;
+
+        for format in Format::ALL {
+            self.remove_format(dir, key, format).await?;
+        }
+
+        Ok(())
+    }
+
+    fn generations(&self) -> usize {
+        self.backups
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SqliteStorage {
+    connection: Arc<SyncMutex<Connection>>,
+}
+
+impl SqliteStorage {
+    pub const FILE: &str = "data.sqlite3";
+
+    pub fn path() -> PathBuf {
+        PathBuf::from(FileStorage::DIR).join(Self::FILE)
+    }
+
+    pub fn open() -> Result<Self> {
+        std::fs::create_dir_all(FileStorage::DIR)?;
+
+        let connection = Connection::open(Self::path())?;
+
+        connection.execute_batch(
+            "PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS records (
+                dir TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (dir, key)
+            );",
+        )?;
+
+        let connection = Arc::new(SyncMutex::new(connection));
+
+        Ok(Self { connection })
+    }
+
+    fn to_json(format: Format, bytes: Vec<u8>) -> Result<String> {
+        Ok(match format {
+            Format::MessagePack => {
+                serde_json::to_string(&format.decode::<serde_json::Value>(&bytes)?)?
+            }
+            Format::Json => String::from_utf8(bytes)?,
+            Format::Ron => return Err(anyhow!("RON records cannot be stored in SQLite")),
+        })
+    }
+
+    async fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+
+        spawn_blocking(move || {
+            let connection = connection
+                .lock()
+                .map_err(|_| anyhow!("poisoned database lock"))?;
+
+            f(&connection).map_err(Into::into)
+        })
+        .await?
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn dirs(&self) -> Result<Vec<String>> {
+        self.with(|c| {
+            let mut statement = c.prepare("SELECT DISTINCT dir FROM records ORDER BY dir")?;
+            let rows = statement.query_map([], |row| row.get(0))?;
+
+            rows.collect()
+        })
+        .await
+    }
+    async fn keys(&self, dir: &str) -> Result<Vec<String>> {
+        let dir = dir.to_string();
+
+        self.with(move |c| {
+            let mut statement = c.prepare("SELECT key FROM records WHERE dir = ?1 ORDER BY key")?;
+            let rows = statement.query_map(params![dir], |row| row.get(0))?;
+
+            rows.collect()
+        })
+        .await
+    }
+    async fn exists(&self, dir: &str, key: &str) -> Result<bool> {
+        let (dir, key) = (dir.to_string(), key.to_string());
+
+        self.with(move |c| {
+            c.query_row(
+                "SELECT 1 FROM records WHERE dir = ?1 AND key = ?2",
+                params![dir, key],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+        })
+        .await
+    }
+    async fn read(&self, dir: &str, key: &str, generation: usize) -> Result<(Format, Vec<u8>)> {
+        if generation > 0 {
+            return Err(anyhow!("missing backup generation {generation}"));
+        }
+
+        let (dir, key) = (dir.to_string(), key.to_string());
+        let value: String = self
+            .with(move |c| {
+                c.query_row(
+                    "SELECT value FROM records WHERE dir = ?1 AND key = ?2",
+                    params![dir, key],
+                    |row| row.get(0),
+                )
+            })
+            .await?;
+
+        Ok((Format::Json, value.into_bytes()))
+    }
+    async fn write(&self, dir: &str, key: &str, format: Format, bytes: Vec<u8>) -> Result<()> {
+        let (dir, key) = (dir.to_string(), key.to_string());
+        let value = Self::to_json(format, bytes)?;
+
+        self.with(move |c| {
+            c.execute(
+                "INSERT INTO records (dir, key, value) VALUES (?1, ?2, ?3)
+                ON CONFLICT (dir, key) DO UPDATE SET value = excluded.value",
+                params![dir, key, value],
+            )
+            .map(|_| ())
+        })
+        .await
+    }
+    async fn remove(&self, dir: &str, key: &str) -> Result<()> {
+        let (dir, key) = (dir.to_string(), key.to_string());
+
+        self.with(move |c| {
+            c.execute(
+                "DELETE FROM records WHERE dir = ?1 AND key = ?2",
+                params![dir, key],
+            )
+            .map(|_| ())
+        })
+        .await
+    }
+
+    async fn values(&self, dir: &str) -> Result<Vec<(String, Result<(Format, Vec<u8>)>)>> {
+        let dir = dir.to_string();
+        let rows: Vec<(String, String)> = self
+            .with(move |c| {
+                let query = "SELECT key, value FROM records WHERE dir = ?1 ORDER BY key";
+                let mut statement = c.prepare(query)?;
+                let rows =
+                    statement.query_map(params![dir], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+                rows.collect()
+            })
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(key, value)| (key, Ok((Format::Json, value.into_bytes()))))
+            .collect())
+    }
+
+    fn format(&self, _: Format) -> Format {
+        Format::Json
+    }
+}
+
+#[cfg(test)]
+pub fn temp_storage(name: &str, backups: usize) -> Result<FileStorage> {
+    let root = std::env::temp_dir().join(format!("storage-{name}-{}", std::process::id()));
+
+    if root.exists() {
+        std::fs::remove_dir_all(&root)?;
+    }
+
+    Ok(FileStorage::with_root(root, backups))
+}
+
+pub async fn copy_records(from: &dyn Storage, to: &dyn Storage) -> Result<usize> {
+    let mut count = 0;
+
+    for dir in from.dirs().await? {
+        for (key, value) in from.values(&dir).await? {
+            let (format, bytes) = value.map_err(|error| anyhow!("{dir}/{key}: {error}"))?;
+
+            to.write(&dir, &key, format, bytes).await?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_records_atomically() -> Result<()> {
+        let storage = temp_storage("atomic", 0)?;
+
+        storage
+            .write("dir", "key", Format::Json, b"1".to_vec())
+            .await?;
+        storage
+            .write("dir", "key", Format::Json, b"2".to_vec())
+            .await?;
+
+        let temp = storage
+            .path_from("dir", "key", Format::Json)?
+            .with_extension(format!("{}.{}", Format::Json.ext(), FileStorage::TMP));
+
+        assert!(!temp.exists());
+        assert_eq!(
+            storage.read("dir", "key", 0).await?,
+            (Format::Json, b"2".to_vec())
+        );
+        assert_eq!(storage.keys("dir").await?, ["key"]);
+
+        storage
+            .write("dir", "key", Format::Ron, b"3".to_vec())
+            .await?;
+
+        assert!(!storage.path_from("dir", "key", Format::Json)?.exists());
+        assert_eq!(
+            storage.read("dir", "key", 0).await?,
+            (Format::Ron, b"3".to_vec())
+        );
+        assert_eq!(storage.keys("dir").await?, ["key"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rotates_backups() -> Result<()> {
+        let storage = temp_storage("rotate", 2)?;
+
+        for value in [b"1", b"2", b"3", b"4"] {
+            storage
+                .write("dir", "key", Format::Json, value.to_vec())
+                .await?;
+        }
+
+        assert_eq!(storage.read("dir", "key", 0).await?.1, b"4");
+        assert_eq!(storage.read("dir", "key", 1).await?.1, b"3");
+        assert_eq!(storage.read("dir", "key", 2).await?.1, b"2");
+        assert!(storage.read("dir", "key", 3).await.is_err());
+        assert!(!storage
+            .backup_path_from("dir", "key", Format::Json, 3)?
+            .exists());
+        assert_eq!(storage.keys("dir").await?, ["key"]);
+
+        storage.remove("dir", "key").await?;
+
+        assert!(!storage.exists("dir", "key").await?);
+        assert!(storage.read("dir", "key", 1).await.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use std::{mem::take, sync::Mutex as SyncMutex};
+
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::prelude::*;
+
+#[derive(Debug, Default)]
+struct Entry<T> {
+    value: T,
+    loaded: bool,
+    dirty: bool,
+    used: Option<DateTime<Utc>>,
+}
+
+type Slot<T> = Arc<Mutex<Entry<T>>>;
+
+#[derive(Debug)]
+pub struct Store<T> {
+    storage: &'static dyn Storage,
+    dir: &'static str,
+    slots: Arc<SyncMutex<BTreeMap<String, Slot<T>>>>,
+}
+
+impl<T> Store<T>
+where
+    T: Versioned + Default + Send + 'static,
+{
+    pub const IDLE_SECS: i64 = 300;
+
+    pub fn new(storage: &'static dyn Storage, dir: &'static str) -> Self {
+        let slots = Arc::default();
+
+        Self {
+            storage,
+            dir,
+            slots,
+        }
+    }
+
+    fn slot(&self, key: &str) -> Result<Slot<T>> {
+        let mut slots = self
+            .slots
+            .lock()
+            .map_err(|_| anyhow!("poisoned store lock"))?;
+
+        Ok(slots.entry(key.to_string()).or_default().clone())
+    }
+    fn slots(&self) -> Result<Vec<(String, Slot<T>)>> {
+        let slots = self
+            .slots
+            .lock()
+            .map_err(|_| anyhow!("poisoned store lock"))?;
+
+        Ok(slots.iter().map(|(k, s)| (k.clone(), s.clone())).collect())
+    }
+
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        let mut keys = self.storage.keys(self.dir).await?;
+
+        keys.extend(self.slots()?.into_iter().map(|(key, _)| key));
+        keys.sort();
+        keys.dedup();
+
+        Ok(keys)
+    }
+    pub async fn lock(&self, key: &str) -> Result<StoreGuard<T>> {
+        let mut entry = self.slot(key)?.lock_owned().await;
+
+        if !entry.loaded {
+            entry.value = if self.storage.exists(self.dir, key).await? {
+                Data::<T>::load_from(self.storage, self.dir, key)
+                    .await?
+                    .res_unwrap()
+            } else {
+                T::default()
+            };
+            entry.loaded = true;
+        }
+
+        entry.used = Some(Utc::now());
+
+        Ok(StoreGuard(entry))
+    }
+    pub async fn delete(&self, key: &str) -> Result<bool> {
+        let mut entry = self.slot(key)?.lock_owned().await;
+
+        entry.value = T::default();
+        entry.loaded = true;
+        entry.dirty = false;
+
+        if !self.storage.exists(self.dir, key).await? {
+            return Ok(false);
+        }
+
+        self.storage.remove(self.dir, key).await?;
+
+        Ok(true)
+    }
+    pub async fn flush(&self) -> Result<usize> {
+        let mut flushed = 0;
+
+        for (key, slot) in self.slots()? {
+            let mut entry = slot.lock().await;
+
+            if !entry.dirty {
+                continue;
+            }
+
+            let data = Data::new(self.dir, &key, take(&mut entry.value));
+            let result = data.res_save_to(self.storage).await;
+
+            entry.value = data.res_unwrap();
+            result?;
+
+            entry.dirty = false;
+            flushed += 1;
+        }
+
+        self.evict()?;
+
+        Ok(flushed)
+    }
+    fn evict(&self) -> Result<()> {
+        let mut slots = self
+            .slots
+            .lock()
+            .map_err(|_| anyhow!("poisoned store lock"))?;
+        let now = Utc::now();
+
+        slots.retain(|_, slot| {
+            if Arc::strong_count(slot) > 1 {
+                return true;
+            }
+
+            slot.try_lock().map_or(true, |entry| {
+                entry.dirty
+                    || entry
+                        .used
+                        .is_some_and(|used| (now - used).num_seconds() < Self::IDLE_SECS)
+            })
+        });
+
+        Ok(())
+    }
+}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage,
+            dir: self.dir,
+            slots: self.slots.clone(),
+        }
+    }
+}
+
+pub struct StoreGuard<T>(OwnedMutexGuard<Entry<T>>);
+
+impl<T> Deref for StoreGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.value
+    }
+}
+
+impl<T> DerefMut for StoreGuard<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.dirty = true;
+
+        &mut self.0.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    struct Record {
+        value: u32,
+    }
+
+    impl Versioned for Record {}
+
+    fn store(name: &str) -> Result<Store<Record>> {
+        let storage = Box::leak(Box::new(temp_storage(name, 0)?));
+
+        Ok(Store::new(storage, "dir"))
+    }
+
+    #[tokio::test]
+    async fn flushes_dirty_entries() -> Result<()> {
+        let store = store("flush")?;
+
+        store.lock("a").await?.value = 1;
+
+        assert_eq!(store.lock("b").await?.value, 0);
+        assert!(!store.storage.exists("dir", "a").await?);
+        assert_eq!(store.flush().await?, 1);
+        assert_eq!(store.flush().await?, 0);
+        assert!(!store.storage.exists("dir", "b").await?);
+
+        let data = Data::<Record>::load_from(store.storage, "dir", "a").await?;
+
+        assert_eq!(data.res_unwrap(), Record { value: 1 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn evicts_idle_entries() -> Result<()> {
+        let store = store("evict")?;
+        let idle = Utc::now() - chrono::Duration::seconds(Store::<Record>::IDLE_SECS + 1);
+
+        store.lock("a").await?.value = 1;
+        store.lock("b").await?.value = 2;
+        store.flush().await?;
+
+        store.lock("a").await?.0.used = Some(idle);
+
+        let mut held = store.lock("b").await?;
+        held.0.used = Some(idle);
+
+        store.evict()?;
+
+        let keys: Vec<_> = store.slots()?.into_iter().map(|(key, _)| key).collect();
+
+        assert_eq!(keys, ["b"]);
+
+        drop(held);
+
+        assert_eq!(store.lock("a").await?.value, 1);
+
+        Ok(())
+    }
+}
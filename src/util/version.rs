@@ -0,0 +1,327 @@
+use serde::de::DeserializeOwned;
+
+use crate::prelude::*;
+
+pub type Migration = fn(Format, &[u8]) -> Result<Vec<u8>>;
+
+pub trait Versioned: Serialize + DeserializeOwned + 'static {
+    const VERSION: u32 = 0;
+    const MIGRATIONS: &'static [Migration] = &[];
+
+    fn migrate(version: u32, format: Format, data: &[u8]) -> Result<Vec<u8>> {
+        if version > Self::VERSION {
+            return Err(anyhow!(
+                "record version {version} is newer than supported version {}",
+                Self::VERSION
+            ));
+        }
+
+        let mut data = data.to_vec();
+
+        for from in version..Self::VERSION {
+            let Some(migration) = Self::MIGRATIONS.get(usize::try_from(from)?) else {
+                return Err(anyhow!("missing migration from version {from}"));
+            };
+
+            data = migration(format, &data)?;
+        }
+
+        Ok(data)
+    }
+}
+
+impl Versioned for serde_json::Value {
+    fn migrate(_: u32, _: Format, data: &[u8]) -> Result<Vec<u8>> {
+        Ok(data.to_vec())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Envelope<V> {
+    #[serde(rename = "__version")]
+    version: u32,
+    #[serde(rename = "__value")]
+    value: V,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename = "Envelope")]
+struct Header {
+    #[serde(rename = "__version")]
+    version: u32,
+}
+
+const fn is_map(data: &[u8]) -> bool {
+    matches!(data.first(), Some(0x80..=0x8F | 0xDE | 0xDF))
+}
+
+fn version_of(format: Format, data: &[u8]) -> Option<u32> {
+    if format == Format::MessagePack && !is_map(data) {
+        return None;
+    }
+
+    format.decode::<Header>(data).ok().map(|h| h.version)
+}
+
+pub fn encode<T: Versioned>(format: Format, value: &T) -> Result<Vec<u8>> {
+    format.encode(&Envelope {
+        version: T::VERSION,
+        value,
+    })
+}
+pub fn decode<T: Versioned>(format: Format, data: &[u8]) -> Result<T> {
+    match version_of(format, data) {
+        Some(version) if version == T::VERSION => decode_value(format, data),
+        None if T::VERSION == 0 => format.decode(data),
+        version => {
+            let data = T::migrate(version.unwrap_or_default(), format, data)?;
+
+            decode_value(format, &data)
+        }
+    }
+}
+pub fn decode_value<V: DeserializeOwned>(format: Format, data: &[u8]) -> Result<V> {
+    if version_of(format, data).is_none() {
+        return format.decode(data);
+    }
+
+    format
+        .decode::<Envelope<V>>(data)
+        .map(|envelope| envelope.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: [(Format, &[u8]); 3] = [
+        (
+            Format::MessagePack,
+            include_bytes!("../../tests/fixtures/collection-legacy.rmp"),
+        ),
+        (
+            Format::Json,
+            include_bytes!("../../tests/fixtures/collection-legacy.json"),
+        ),
+        (
+            Format::Ron,
+            include_bytes!("../../tests/fixtures/collection-legacy.ron"),
+        ),
+    ];
+    const V0: [(Format, &[u8]); 3] = [
+        (
+            Format::MessagePack,
+            include_bytes!("../../tests/fixtures/collection-v0.rmp"),
+        ),
+        (
+            Format::Json,
+            include_bytes!("../../tests/fixtures/collection-v0.json"),
+        ),
+        (
+            Format::Ron,
+            include_bytes!("../../tests/fixtures/collection-v0.ron"),
+        ),
+    ];
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct CollectionV1 {
+        species: BTreeMap<i64, usize>,
+        shinies: usize,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct CollectionV2 {
+        species: BTreeMap<i64, usize>,
+        shinies: usize,
+        total: usize,
+    }
+
+    impl CollectionV1 {
+        fn from_v0(format: Format, data: &[u8]) -> Result<Vec<u8>> {
+            let collection: Collection = decode_value(format, data)?;
+            let mut species = BTreeMap::new();
+
+            for caught in &collection.pokemon {
+                *species.entry(caught.species_id).or_default() += 1;
+            }
+
+            let shinies = collection.pokemon.iter().filter(|c| c.shiny).count();
+
+            format.encode(&Self { species, shinies })
+        }
+    }
+
+    impl CollectionV2 {
+        fn from_v1(format: Format, data: &[u8]) -> Result<Vec<u8>> {
+            let CollectionV1 { species, shinies } = decode_value(format, data)?;
+            let total = species.values().sum();
+
+            format.encode(&Self {
+                species,
+                shinies,
+                total,
+            })
+        }
+    }
+
+    impl Versioned for CollectionV1 {
+        const VERSION: u32 = 1;
+        const MIGRATIONS: &'static [Migration] = &[Self::from_v0];
+    }
+
+    impl Versioned for CollectionV2 {
+        const VERSION: u32 = 2;
+        const MIGRATIONS: &'static [Migration] = &[CollectionV1::from_v0, Self::from_v1];
+    }
+
+    fn caught(species_id: i64, name: &str, shiny: bool) -> Result<Caught> {
+        Ok(Caught {
+            species_id,
+            name: name.to_string(),
+            types: vec![],
+            nickname: None,
+            level: 0,
+            shiny,
+            ivs: [0; 6],
+            caught_at: "2023-01-14T18:30:00Z".parse()?,
+            anchor: None,
+        })
+    }
+    fn expected() -> Result<Collection> {
+        let pikachu = Caught {
+            types: vec!["Electric".to_string()],
+            nickname: Some("Sparky".to_string()),
+            level: 12,
+            ivs: [31, 20, 15, 8, 0, 27],
+            ..caught(25, "Pikachu", false)?
+        };
+        let charizard = Caught {
+            types: vec!["Fire".to_string(), "Flying".to_string()],
+            level: 36,
+            ivs: [1, 2, 3, 4, 5, 6],
+            caught_at: "2023-02-03T09:15:42Z".parse()?,
+            ..caught(6, "Charizard", true)?
+        };
+
+        Ok(Collection {
+            pokemon: vec![pikachu, charizard],
+        })
+    }
+
+    #[test]
+    fn decodes_legacy_records() -> Result<()> {
+        for (format, data) in LEGACY {
+            assert_eq!(version_of(format, data), None, "{format:?}");
+            assert_eq!(
+                decode::<Collection>(format, data)?,
+                expected()?,
+                "{format:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn decodes_enveloped_records() -> Result<()> {
+        for (format, data) in V0 {
+            assert_eq!(version_of(format, data), Some(0), "{format:?}");
+            assert_eq!(
+                decode::<Collection>(format, data)?,
+                expected()?,
+                "{format:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_records() -> Result<()> {
+        for format in Format::ALL {
+            let data = encode(format, &expected()?)?;
+
+            assert_eq!(
+                decode::<Collection>(format, &data)?,
+                expected()?,
+                "{format:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn migrates_v0_to_v1() -> Result<()> {
+        let migrated = CollectionV1 {
+            species: BTreeMap::from([(6, 1), (25, 1)]),
+            shinies: 1,
+        };
+
+        for (format, data) in LEGACY.into_iter().chain(V0) {
+            assert_eq!(
+                decode::<CollectionV1>(format, data)?,
+                migrated,
+                "{format:?}"
+            );
+        }
+        for format in Format::ALL {
+            let data = encode(format, &migrated)?;
+
+            assert_eq!(version_of(format, &data), Some(1), "{format:?}");
+            assert_eq!(
+                decode::<CollectionV1>(format, &data)?,
+                migrated,
+                "{format:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn migrates_one_version_at_a_time() -> Result<()> {
+        let v1 = CollectionV1 {
+            species: BTreeMap::from([(6, 1), (25, 1)]),
+            shinies: 1,
+        };
+        let v2 = CollectionV2 {
+            species: v1.species.clone(),
+            shinies: 1,
+            total: 2,
+        };
+
+        for (format, data) in LEGACY.into_iter().chain(V0) {
+            assert_eq!(decode::<CollectionV2>(format, data)?, v2, "{format:?}");
+        }
+        for format in Format::ALL {
+            let data = encode(format, &v1)?;
+
+            assert_eq!(decode::<CollectionV2>(format, &data)?, v2, "{format:?}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_newer_versions() -> Result<()> {
+        let newer = CollectionV1 {
+            species: BTreeMap::new(),
+            shinies: 0,
+        };
+
+        for format in Format::ALL {
+            let data = encode(format, &newer)?;
+            let Err(error) = decode::<Collection>(format, &data) else {
+                return Err(anyhow!("{format:?} record from version 1 was accepted"));
+            };
+
+            assert!(
+                error.to_string().contains("newer than supported"),
+                "{error}"
+            );
+        }
+
+        Ok(())
+    }
+}